@@ -46,3 +46,33 @@ fn new_seed(b: &mut Bencher) {
         let _ = Seed::new(&m, "");
     });
 }
+
+/// `Mnemonic::validate` in every language. These measure warm calls, run
+/// `examples/cold_start.rs` for the first call of each language in a fresh process.
+macro_rules! validate_lang {
+    ($($name:ident, $lang:ident, $feature:literal;)*) => {
+        $(
+            #[bench]
+            #[cfg(feature = $feature)]
+            fn $name(b: &mut Bencher) {
+                let phrase = Mnemonic::from_entropy(&[0x5A; 16], Language::$lang).unwrap();
+
+                b.iter(|| {
+                    let _ = Mnemonic::validate(phrase.phrase(), Language::$lang);
+                });
+            }
+        )*
+    };
+}
+
+validate_lang! {
+    validate_chinese_simplified, ChineseSimplified, "chinese-simplified";
+    validate_chinese_traditional, ChineseTraditional, "chinese-traditional";
+    validate_czech, Czech, "czech";
+    validate_french, French, "french";
+    validate_italian, Italian, "italian";
+    validate_japanese, Japanese, "japanese";
+    validate_korean, Korean, "korean";
+    validate_portuguese, Portuguese, "portuguese";
+    validate_spanish, Spanish, "spanish";
+}
@@ -0,0 +1,162 @@
+//! Generates the static word lists and perfect hash word maps from `src/langs/*.txt`.
+
+use std::env;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+/// File name in `src/langs`, name of the generated tables and the feature that enables them
+const LANGUAGES: &[(&str, &str, Option<&str>)] = &[
+    ("english", "ENGLISH", None),
+    (
+        "chinese_simplified",
+        "CHINESE_SIMPLIFIED",
+        Some("chinese-simplified"),
+    ),
+    (
+        "chinese_traditional",
+        "CHINESE_TRADITIONAL",
+        Some("chinese-traditional"),
+    ),
+    ("czech", "CZECH", Some("czech")),
+    ("french", "FRENCH", Some("french")),
+    ("italian", "ITALIAN", Some("italian")),
+    ("japanese", "JAPANESE", Some("japanese")),
+    ("korean", "KOREAN", Some("korean")),
+    ("portuguese", "PORTUGUESE", Some("portuguese")),
+    ("spanish", "SPANISH", Some("spanish")),
+];
+
+/// Must match `MAX_WORD_BYTES` in `src/language.rs`
+const MAX_WORD_BYTES: usize = 64;
+
+fn main() {
+    let compressed = env::var_os("CARGO_FEATURE_COMPRESSED_WORDLISTS").is_some();
+    let mut out = String::new();
+
+    for &(file, name, feature) in LANGUAGES {
+        let path = format!("src/langs/{}.txt", file);
+
+        println!("cargo:rerun-if-changed={}", path);
+
+        let text = fs::read_to_string(&path).expect("word list is readable");
+        let words: Vec<&str> = text.split_whitespace().collect();
+
+        assert_eq!(words.len(), 2048, "{} must have 2048 words", path);
+        // `Language::verify_wordlist` hashes the words one per line, which only matches the
+        // official hash if the file is laid out exactly that way
+        assert!(
+            text.len() == words.iter().map(|word| word.len() + 1).sum::<usize>()
+                && text.lines().eq(words.iter().copied())
+                && text.ends_with('\n'),
+            "{} must have exactly one word per line",
+            path
+        );
+        assert!(
+            words.iter().all(|word| word.len() <= MAX_WORD_BYTES),
+            "{} has a word too long for constant time lookup",
+            path
+        );
+
+        let mut sorted = words.clone();
+        sorted.sort_unstable();
+
+        let cfg = match feature {
+            Some(feature) => format!("#[cfg(feature = \"{}\")]\n", feature),
+            None => String::new(),
+        };
+
+        if compressed {
+            write_compressed(&mut out, &cfg, name, &words, &sorted);
+        } else {
+            write_static(&mut out, &cfg, name, &words, &sorted);
+        }
+    }
+
+    let dest = Path::new(&env::var("OUT_DIR").unwrap()).join("wordlists.rs");
+
+    fs::write(dest, out).unwrap();
+    println!("cargo:rerun-if-changed=build.rs");
+}
+
+/// Plain word arrays and a perfect hash map, no initialization at runtime
+fn write_static(out: &mut String, cfg: &str, name: &str, words: &[&str], sorted: &[&str]) {
+    let mut map = phf_codegen::Map::new();
+
+    for (idx, word) in words.iter().enumerate() {
+        map.entry(*word, &idx.to_string());
+    }
+
+    writeln!(
+        out,
+        "{cfg}static WORDS_{name}: [&str; 2048] = {words:?};\n\
+         {cfg}static SORTED_{name}: [&str; 2048] = {sorted:?};\n\
+         {cfg}static MAP_{name}: phf::Map<&'static str, u16> = {map};\n\
+         {cfg}pub static WORDLIST_{name}: WordList = \
+             WordList::from_static(&WORDS_{name}, &SORTED_{name});\n\
+         {cfg}pub static WORDMAP_{name}: WordMap = WordMap {{ \
+             inner: Lookup::Static(&MAP_{name}) }};",
+        cfg = cfg,
+        name = name,
+        words = words,
+        sorted = sorted,
+        map = map.build(),
+    )
+    .unwrap();
+}
+
+/// Front coded sorted words, unpacked on first use
+///
+/// Each word is stored as the number of leading bytes shared with the previous word, the number
+/// of remaining bytes, and the remaining bytes. Lists that aren't sorted also store the list
+/// position of each sorted word.
+fn write_compressed(out: &mut String, cfg: &str, name: &str, words: &[&str], sorted: &[&str]) {
+    let mut packed = Vec::new();
+    let mut previous: &[u8] = &[];
+
+    for word in sorted {
+        let word = word.as_bytes();
+        let shared = previous
+            .iter()
+            .zip(word)
+            .take_while(|(a, b)| a == b)
+            .count();
+
+        let suffix = word.len() - shared;
+
+        assert!(
+            shared <= u8::MAX as usize && suffix <= u8::MAX as usize,
+            "{} has a word too long to front code",
+            name
+        );
+
+        packed.push(shared as u8);
+        packed.push(suffix as u8);
+        packed.extend_from_slice(&word[shared..]);
+        previous = word;
+    }
+
+    let order: Vec<u16> = if words == sorted {
+        Vec::new()
+    } else {
+        sorted
+            .iter()
+            .map(|word| words.iter().position(|w| w == word).unwrap() as u16)
+            .collect()
+    };
+
+    writeln!(
+        out,
+        "{cfg}static PACKED_{name}: &[u8] = &{packed:?};\n\
+         {cfg}static ORDER_{name}: &[u16] = &{order:?};\n\
+         {cfg}pub static WORDLIST_{name}: Lazy<WordList> = \
+             Lazy::new(|| WordList::unpack(PACKED_{name}, ORDER_{name}));\n\
+         {cfg}pub static WORDMAP_{name}: Lazy<WordMap> = \
+             Lazy::new(|| WordMap::from_wordlist(&WORDLIST_{name}));",
+        cfg = cfg,
+        name = name,
+        packed = packed,
+        order = order,
+    )
+    .unwrap();
+}
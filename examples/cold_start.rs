@@ -0,0 +1,76 @@
+//! Time the first `Mnemonic::validate` of each language in a fresh process, next to the average
+//! of later calls.
+//!
+//! ```text
+//! cargo run --release --example cold_start
+//! cargo run --release --example cold_start --features compressed-wordlists
+//! ```
+//!
+//! Nothing touches the word lists before they are timed, so the first call of each language
+//! includes whatever it takes to make that list usable.
+
+use bip39::{Language, Mnemonic};
+use std::time::Instant;
+use unicode_normalization::UnicodeNormalization;
+
+/// Calls averaged for the time of a warm call
+const WARM_CALLS: u32 = 10_000;
+
+fn main() {
+    // The first and the fourth word of each list. The phrase of all zero entropy is the first
+    // word eleven times, followed by the fourth.
+    let languages = [
+        (Language::English, "abandon", "about"),
+        #[cfg(feature = "chinese-simplified")]
+        (Language::ChineseSimplified, "的", "在"),
+        #[cfg(feature = "chinese-traditional")]
+        (Language::ChineseTraditional, "的", "在"),
+        #[cfg(feature = "czech")]
+        (Language::Czech, "abdikace", "agrese"),
+        #[cfg(feature = "french")]
+        (Language::French, "abaisser", "abeille"),
+        #[cfg(feature = "italian")]
+        (Language::Italian, "abaco", "abete"),
+        #[cfg(feature = "japanese")]
+        (Language::Japanese, "あいこくしん", "あおぞら"),
+        #[cfg(feature = "korean")]
+        (Language::Korean, "가격", "가능"),
+        #[cfg(feature = "portuguese")]
+        (Language::Portuguese, "abacate", "abater"),
+        #[cfg(feature = "spanish")]
+        (Language::Spanish, "ábaco", "abierto"),
+    ];
+
+    println!(
+        "{:<20} {:>12} {:>12}",
+        "language", "first call", "warm call"
+    );
+
+    for &(lang, first, fourth) in &languages {
+        let mut words = vec![first; 11];
+
+        words.push(fourth);
+
+        // Word lists are stored NFKD, and `validate` expects its input that way
+        let phrase: String = words.join(" ").nfkd().collect();
+
+        let start = Instant::now();
+        Mnemonic::validate(&phrase, lang).expect("phrase of zero entropy is valid");
+        let cold = start.elapsed();
+
+        let start = Instant::now();
+
+        for _ in 0..WARM_CALLS {
+            Mnemonic::validate(&phrase, lang).expect("phrase of zero entropy is valid");
+        }
+
+        let warm = start.elapsed() / WARM_CALLS;
+
+        println!(
+            "{:<20} {:>12} {:>12}",
+            format!("{:?}", lang),
+            format!("{:?}", cold),
+            format!("{:?}", warm)
+        );
+    }
+}
@@ -0,0 +1,225 @@
+//! Algorand 25 word mnemonic codec.
+//!
+//! Algorand encodes 32 byte private keys using the BIP39 English word list, but not the BIP39
+//! algorithm: the key is split into 11-bit chunks in *little-endian* order, yielding 24 words,
+//! and a 25th checksum word is taken from the first 11 bits of the SHA-512/256 hash of the key.
+//!
+//! ```
+//! use bip39::algorand;
+//!
+//! let phrase = "advice pudding treat near rule blouse same whisper inner electric quit surface \
+//!               sunny dismiss leader blood seat clown cost exist hospital century reform able sponsor";
+//!
+//! let key = algorand::decode(phrase).unwrap();
+//!
+//! assert_eq!(&*algorand::encode(&key).unwrap(), phrase);
+//! ```
+
+use crate::error::ErrorKind;
+use crate::language::Language;
+use crate::util::{Bits11, IterExt};
+use anyhow::Error;
+use sha2::{Digest, Sha512_256};
+use zeroize::Zeroizing;
+
+/// Size of an Algorand private key in bytes
+pub const KEY_BYTES: usize = 32;
+
+/// Number of words in an Algorand mnemonic, including the checksum word
+pub const WORD_COUNT: usize = 25;
+
+/// Number of words encoding the key itself
+const KEY_WORDS: usize = WORD_COUNT - 1;
+
+/// Encode a 32 byte key as a 25 word Algorand mnemonic
+///
+/// Supplying a key of any other length will return an `Error` of kind
+/// `ErrorKind::InvalidKeysize`.
+pub fn encode(key: &[u8]) -> Result<Zeroizing<String>, Error> {
+    if key.len() != KEY_BYTES {
+        Err(ErrorKind::InvalidKeysize(key.len() * 8))?;
+    }
+
+    let wordlist = Language::English.try_wordlist()?;
+    let indices = Zeroizing::new(to_u11_le(key));
+    let checksum = checksum_index(key);
+
+    Ok(indices
+        .iter()
+        .chain(Some(&checksum))
+        .map(|&idx| wordlist.get_word(Bits11::from(idx)))
+        .join(" "))
+}
+
+/// Decode a 25 word Algorand mnemonic back into the 32 byte key
+///
+/// The phrase is checked for word count and validated against the checksum word.
+pub fn decode(phrase: &str) -> Result<Zeroizing<Vec<u8>>, Error> {
+    let wordmap = Language::English.try_wordmap()?;
+
+    let mut indices = Zeroizing::new(Vec::with_capacity(WORD_COUNT));
+
+    for (idx, word) in phrase.split_whitespace().enumerate() {
+        let bits = wordmap.get_bits(word).ok_or(ErrorKind::InvalidWord(idx))?;
+        indices.push(u16::from(bits));
+    }
+
+    if indices.len() != WORD_COUNT {
+        Err(ErrorKind::InvalidWordLength(indices.len()))?;
+    }
+
+    let checksum = indices[KEY_WORDS];
+    let mut key = Zeroizing::new(from_u11_le(&indices[..KEY_WORDS]));
+
+    // 24 words carry 264 bits, the trailing byte only holds padding and must be empty
+    if key.pop() != Some(0) || checksum != checksum_index(&key) {
+        Err(ErrorKind::InvalidChecksum)?;
+    }
+
+    Ok(key)
+}
+
+/// Validate an Algorand mnemonic phrase
+///
+/// # Example
+///
+/// ```
+/// use bip39::algorand;
+///
+/// let phrase = "advice pudding treat near rule blouse same whisper inner electric quit surface \
+///               sunny dismiss leader blood seat clown cost exist hospital century reform able sponsor";
+///
+/// assert!(algorand::validate(phrase).is_ok());
+/// ```
+pub fn validate(phrase: &str) -> Result<(), Error> {
+    decode(phrase)?;
+
+    Ok(())
+}
+
+/// First 11 bits of SHA-512/256 of the key, read little-endian
+fn checksum_index(key: &[u8]) -> u16 {
+    let hash = Sha512_256::digest(key);
+
+    to_u11_le(&hash[..2])[0]
+}
+
+/// Split bytes into 11-bit values, least significant bits first
+fn to_u11_le(bytes: &[u8]) -> Vec<u16> {
+    let mut out = Vec::with_capacity((bytes.len() * 8).div_ceil(11));
+    let mut buffer = 0u32;
+    let mut bits = 0;
+
+    for &byte in bytes {
+        buffer |= (byte as u32) << bits;
+        bits += 8;
+
+        if bits >= 11 {
+            out.push((buffer & 0x7FF) as u16);
+            buffer >>= 11;
+            bits -= 11;
+        }
+    }
+
+    if bits != 0 {
+        out.push((buffer & 0x7FF) as u16);
+    }
+
+    out
+}
+
+/// Inverse of `to_u11_le`, any trailing partial byte is kept
+fn from_u11_le(values: &[u16]) -> Vec<u8> {
+    let mut out = Vec::with_capacity((values.len() * 11).div_ceil(8));
+    let mut buffer = 0u32;
+    let mut bits = 0;
+
+    for &value in values {
+        buffer |= (value as u32) << bits;
+        bits += 11;
+
+        while bits >= 8 {
+            out.push(buffer as u8);
+            buffer >>= 8;
+            bits -= 8;
+        }
+    }
+
+    if bits != 0 {
+        out.push(buffer as u8);
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    /// Known-good mnemonic from the Algorand SDK test suites
+    const PHRASE: &str = "advice pudding treat near rule blouse same whisper inner electric quit surface sunny dismiss leader blood seat clown cost exist hospital century reform able sponsor";
+    const KEY: &str = "2048abcf3999de60dc97faa3db915fa3bd6cfdd0ef1712064b61fcf4b6958856";
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn reference_vector() {
+        let key = hex::decode(KEY).unwrap();
+
+        assert_eq!(&*decode(PHRASE).unwrap(), &key);
+        assert_eq!(&*encode(&key).unwrap(), PHRASE);
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn zero_key_round_trip() {
+        let phrase = encode(&[0; KEY_BYTES]).unwrap();
+
+        assert_eq!(phrase.split(' ').count(), WORD_COUNT);
+        assert!(phrase.starts_with("abandon abandon"));
+        assert_eq!(&*decode(&phrase).unwrap(), &[0; KEY_BYTES]);
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn invalid_key_size() {
+        assert!(encode(&[0; 31]).is_err());
+        assert!(encode(&[0; 33]).is_err());
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn invalid_checksum() {
+        let phrase = PHRASE.replace("sponsor", "abandon");
+
+        assert!(validate(&phrase).is_err());
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn invalid_word_count() {
+        let phrase = PHRASE.replace(" sponsor", "");
+
+        assert!(validate(&phrase).is_err());
+        assert!(validate("").is_err());
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn invalid_word() {
+        let phrase = PHRASE.replace("pudding", "puddings");
+
+        assert!(validate(&phrase).is_err());
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn u11_le_round_trip() {
+        let bytes: Vec<u8> = (0..KEY_BYTES as u8).collect();
+        let mut decoded = from_u11_le(&to_u11_le(&bytes));
+
+        assert_eq!(decoded.pop(), Some(0));
+        assert_eq!(decoded, bytes);
+    }
+}
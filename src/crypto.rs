@@ -5,13 +5,34 @@
 //! [Seed]: ../seed/struct.Seed.html
 //!
 
+#[cfg(feature = "rand")]
+use crate::error::ErrorKind;
 use hmac::Hmac;
 #[cfg(feature = "rand")]
 use rand::{thread_rng, RngCore};
 use sha2::Digest;
+#[cfg(feature = "rand")]
+use zeroize::Zeroizing;
 
 const PBKDF2_ROUNDS: u32 = 2048;
-const PBKDF2_BYTES: usize = 64;
+pub(crate) const PBKDF2_BYTES: usize = 64;
+
+/// Number of bytes the RNG health tests look at, at least, for each call to `gen_random_bytes`
+#[cfg(feature = "rand")]
+const HEALTH_TEST_WINDOW: usize = 512;
+
+/// Repetition count test cutoff for full entropy bytes
+///
+/// SP 800-90B section 4.4.1 gives `1 + ceil(30 / 8) = 5` for a false positive rate of 2^-30 per
+/// sample, but the test runs over a whole window at every call. A run of 6 starts at any of the
+/// roughly 530 positions with a probability of 2^-40, about 2^-31 per call.
+#[cfg(feature = "rand")]
+const REPETITION_CUTOFF: usize = 6;
+
+/// Adaptive proportion test cutoff for full entropy bytes, a window of 512 and a false positive
+/// rate of 2^-30, per SP 800-90B section 4.4.2
+#[cfg(feature = "rand")]
+const PROPORTION_CUTOFF: usize = 17;
 
 /// SHA256 helper function, internal to the crate
 ///
@@ -21,24 +42,193 @@ pub(crate) fn sha256_first_byte(input: &[u8]) -> u8 {
 
 /// Random byte generator, used to create new mnemonics
 ///
+/// The output is health tested, see `health_test`.
+///
+#[cfg(feature = "rand")]
+pub(crate) fn gen_random_bytes(byte_length: usize) -> Result<Zeroizing<Vec<u8>>, ErrorKind> {
+    gen_tested_bytes(&mut thread_rng(), byte_length)
+}
+
+/// Draw enough blocks of `byte_length` to fill the health test window, test all of them, and
+/// return the last one
 #[cfg(feature = "rand")]
-pub(crate) fn gen_random_bytes(byte_length: usize) -> Vec<u8> {
-    let mut rng = thread_rng();
-    let mut bytes = vec![0u8; byte_length];
+fn gen_tested_bytes<R: RngCore>(
+    rng: &mut R,
+    byte_length: usize,
+) -> Result<Zeroizing<Vec<u8>>, ErrorKind> {
+    let mut stream = Zeroizing::new(vec![0u8; stream_len(byte_length)]);
 
-    rng.fill_bytes(&mut bytes);
+    rng.fill_bytes(&mut stream);
+    health_test(&stream, byte_length)?;
 
-    bytes
+    Ok(Zeroizing::new(
+        stream[stream.len() - byte_length..].to_vec(),
+    ))
 }
+
+/// Number of bytes drawn to return `byte_length` tested bytes, a whole number of blocks filling
+/// the health test window
+#[cfg(feature = "rand")]
+fn stream_len(byte_length: usize) -> usize {
+    HEALTH_TEST_WINDOW.div_ceil(byte_length).max(2) * byte_length
+}
+
+/// Continuous health tests in the style of SP 800-90B, treating each byte as a sample
+///
+/// A working RNG fails these about once in 2^30 calls, they only catch an RNG that is broken
+/// outright, such as one stuck returning zeroes or repeating its output.
+///
+/// * The repetition count test fails when a byte repeats `REPETITION_CUTOFF` times in a row.
+/// * The adaptive proportion test fails when the first byte of a window turns up
+///   `PROPORTION_CUTOFF` times in that window.
+/// * The stuck output test fails when a block of `block_len` bytes is the same as the one before.
+#[cfg(feature = "rand")]
+fn health_test(stream: &[u8], block_len: usize) -> Result<(), ErrorKind> {
+    let mut run = 1;
+
+    for pair in stream.windows(2) {
+        if pair[0] != pair[1] {
+            run = 1;
+            continue;
+        }
+
+        run += 1;
+
+        if run >= REPETITION_CUTOFF {
+            return Err(ErrorKind::RngHealthTest("repetition count"));
+        }
+    }
+
+    for window in stream.chunks(HEALTH_TEST_WINDOW) {
+        let count = window.iter().filter(|&&byte| byte == window[0]).count();
+
+        if count >= PROPORTION_CUTOFF {
+            return Err(ErrorKind::RngHealthTest("adaptive proportion"));
+        }
+    }
+
+    let mut blocks = stream.chunks_exact(block_len);
+
+    if let Some(mut previous) = blocks.next() {
+        for block in blocks {
+            if block == previous {
+                return Err(ErrorKind::RngHealthTest("stuck output"));
+            }
+
+            previous = block;
+        }
+    }
+
+    Ok(())
+}
+
 /// PBKDF2 helper, used to generate [`Seed`][Seed] from [`Mnemonic`][Mnemonic]
 ///
+/// The output is written straight into `seed`, so that no copy of it is left behind.
+///
 /// [Mnemonic]: ../mnemonic/struct.Mnemonic.html
 /// [Seed]: ../seed/struct.Seed.html
 ///
-pub(crate) fn pbkdf2(input: &[u8], salt: &str) -> Vec<u8> {
-    let mut seed = vec![0u8; PBKDF2_BYTES];
+pub(crate) fn pbkdf2(input: &[u8], salt: &[u8], seed: &mut [u8]) {
+    pbkdf2::pbkdf2::<Hmac<sha2::Sha512>>(input, salt, PBKDF2_ROUNDS, seed);
+}
+
+#[cfg(all(test, feature = "rand"))]
+mod test {
+    use super::*;
+    use rand::rngs::mock::StepRng;
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn working_rng_passes() {
+        for &len in &[16, 20, 24, 28, 32] {
+            for _ in 0..100 {
+                assert_eq!(gen_random_bytes(len).unwrap().len(), len);
+            }
+        }
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn broken_rng_fails() {
+        assert!(gen_tested_bytes(&mut StepRng::new(0, 0), 16).is_err());
+        assert!(gen_tested_bytes(&mut StepRng::new(0x0123_4567_89ab_cdef, 0), 32).is_err());
+    }
+
+    /// The chance that a working RNG fails a call, for the longest stream any entropy size draws
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn false_positive_rate() {
+        let p = 1.0f64 / 256.0;
+        let len = [16, 20, 24, 28, 32]
+            .iter()
+            .map(|&len| stream_len(len))
+            .max()
+            .unwrap();
+
+        // Union bound over every position a run of REPETITION_CUTOFF equal bytes can start at
+        let starts = (len - REPETITION_CUTOFF + 1) as f64;
+        let repetition = starts * p.powi(REPETITION_CUTOFF as i32 - 1);
+
+        // Binomial tail of the other samples of a full window matching its first byte
+        let n = HEALTH_TEST_WINDOW - 1;
+        let mut term = (1.0 - p).powi(n as i32);
+        let mut proportion = 0.0;
+
+        for k in 0..n {
+            if k >= PROPORTION_CUTOFF - 1 {
+                proportion += term;
+            }
+
+            term *= (n - k) as f64 / (k + 1) as f64 * p / (1.0 - p);
+        }
+
+        assert!(len < 2 * HEALTH_TEST_WINDOW);
+        assert!(repetition < 2f64.powi(-30), "{}", repetition.log2());
+        assert!(proportion < 2f64.powi(-31), "{}", proportion.log2());
+        assert!(repetition + proportion < 2f64.powi(-30));
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn health_tests() {
+        let mut stream = [0u8; 512];
+
+        thread_rng().fill_bytes(&mut stream);
+        // The adaptive proportion test counts the first byte of the window
+        stream[0] = 1;
+
+        let mut repeated = stream;
+
+        repeated[99] = 6;
+        repeated[100..105].copy_from_slice(&[7; 5]);
+        repeated[105] = 8;
+        assert!(health_test(&repeated, 16).is_ok());
+
+        repeated[105] = 7;
+        assert_eq!(
+            health_test(&repeated, 16).unwrap_err().to_string(),
+            "random number generator failed the repetition count health test"
+        );
+
+        let mut biased = stream;
+
+        for byte in biased.iter_mut().skip(20).step_by(30) {
+            *byte = 1;
+        }
+        assert_eq!(
+            health_test(&biased, 16).unwrap_err().to_string(),
+            "random number generator failed the adaptive proportion health test"
+        );
 
-    pbkdf2::pbkdf2::<Hmac<sha2::Sha512>>(input, salt.as_bytes(), PBKDF2_ROUNDS, &mut seed);
+        let mut stuck = stream;
 
-    seed
+        stuck.copy_within(256..272, 272);
+        assert_eq!(
+            health_test(&stuck, 16).unwrap_err().to_string(),
+            "random number generator failed the stuck output health test"
+        );
+    }
 }
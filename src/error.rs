@@ -13,6 +13,24 @@ pub enum ErrorKind {
     InvalidWordLength(usize),
     #[error("invalid entropy length {0}bits for mnemonic type {1:?}")]
     InvalidEntropyLength(usize, MnemonicType),
+    #[error("invalid keystore: {0}")]
+    InvalidKeystore(&'static str),
+    #[error("unable to decrypt keystore, wrong password or corrupted data")]
+    KeystoreDecryption,
+    #[error("invalid SeedQR data")]
+    InvalidSeedQr,
+    #[error("invalid UR: {0}")]
+    InvalidUr(&'static str),
+    #[error("four letter stamp of word with index {0} matches more than one word")]
+    AmbiguousStamp(usize),
+    #[error("invalid word list: {0}")]
+    InvalidWordList(&'static str),
+    #[error("self test failed: {0}")]
+    SelfTestFailed(&'static str),
+    #[error("random number generator failed the {0} health test")]
+    RngHealthTest(&'static str),
+    #[error("seed gives an invalid BIP32 master key")]
+    InvalidMasterKey,
 }
 
 #[cfg(test)]
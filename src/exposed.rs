@@ -0,0 +1,77 @@
+use crate::mnemonic::Mnemonic;
+use crate::seed::Seed;
+use std::fmt;
+
+/// Formatting wrapper that reveals the secret value of a [`Mnemonic`][Mnemonic] or [`Seed`][Seed].
+///
+/// Neither type implements `Display` or the hex formatting traits directly, and their `Debug`
+/// output is redacted, so that a stray `{:?}` in a log line or panic message can't leak a wallet.
+/// To format the secret, ask for it explicitly with [`Mnemonic::expose()`][Mnemonic::expose()]
+/// or [`Seed::expose()`][Seed::expose()]:
+///
+/// ```
+/// use bip39::{Mnemonic, Language};
+///
+/// let entropy = &[0x33, 0xE4, 0x6B, 0xB1, 0x3A, 0x74, 0x6E, 0xA4, 0x1C, 0xDD, 0xE4, 0x5C, 0x90, 0x84, 0x6A, 0x79];
+/// let mnemonic = Mnemonic::from_entropy(entropy, Language::English).unwrap();
+///
+/// assert_eq!(format!("{:?}", mnemonic), "Mnemonic { language: English, words: 12, .. }");
+/// assert_eq!(format!("{}", mnemonic.expose()), mnemonic.phrase());
+/// assert_eq!(format!("{:x}", mnemonic.expose()), "33e46bb13a746ea41cdde45c90846a79");
+/// ```
+///
+/// `Display` shows the phrase of a [`Mnemonic`][Mnemonic], while `LowerHex` and `UpperHex` show
+/// the entropy of a [`Mnemonic`][Mnemonic] or the bytes of a [`Seed`][Seed]. The alternate flag
+/// (`{:#x}`) adds a `0x` prefix.
+///
+/// [Mnemonic]: ./mnemonic/struct.Mnemonic.html
+/// [Mnemonic::expose()]: ./mnemonic/struct.Mnemonic.html#method.expose
+/// [Seed]: ./seed/struct.Seed.html
+/// [Seed::expose()]: ./seed/struct.Seed.html#method.expose
+pub struct Exposed<'a, T: ?Sized>(pub(crate) &'a T);
+
+impl fmt::Display for Exposed<'_, Mnemonic> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self.0.phrase(), f)
+    }
+}
+
+impl fmt::LowerHex for Exposed<'_, Mnemonic> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_hex(f, self.0.entropy(), false)
+    }
+}
+
+impl fmt::UpperHex for Exposed<'_, Mnemonic> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_hex(f, self.0.entropy(), true)
+    }
+}
+
+impl fmt::LowerHex for Exposed<'_, Seed> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_hex(f, self.0.as_bytes(), false)
+    }
+}
+
+impl fmt::UpperHex for Exposed<'_, Seed> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_hex(f, self.0.as_bytes(), true)
+    }
+}
+
+fn write_hex(f: &mut fmt::Formatter, bytes: &[u8], upper: bool) -> fmt::Result {
+    if f.alternate() {
+        f.write_str("0x")?;
+    }
+
+    for byte in bytes {
+        if upper {
+            write!(f, "{:02X}", byte)?;
+        } else {
+            write!(f, "{:02x}", byte)?;
+        }
+    }
+
+    Ok(())
+}
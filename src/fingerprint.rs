@@ -0,0 +1,156 @@
+//! BIP32 master key fingerprint of a [`Seed`][Seed].
+//!
+//! Every passphrase opens a wallet, so a mistyped one opens the wrong wallet without any error.
+//! Showing the fingerprint, the first four bytes of the HASH160 of the master public key, lets a
+//! user confirm that they reached the same wallet as before, without revealing anything useful
+//! about the seed. It is the same value wallets show as the master fingerprint in descriptors
+//! and PSBTs.
+//!
+//! [Seed]: ../seed/struct.Seed.html
+
+use crate::error::ErrorKind;
+use crate::language::Language;
+use crate::seed::Seed;
+use crate::util::Bits11;
+use anyhow::Error;
+use hmac::{Hmac, Mac};
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use k256::SecretKey;
+use ripemd::Ripemd160;
+use sha2::{Digest, Sha256, Sha512};
+use zeroize::Zeroize;
+
+/// HMAC key used to derive the BIP32 master key from a seed
+const MASTER_KEY_SALT: &[u8] = b"Bitcoin seed";
+
+/// Number of bytes in a fingerprint
+const FINGERPRINT_BYTES: usize = 4;
+
+impl Seed {
+    /// Get the BIP32 master key fingerprint
+    ///
+    /// Returns an `Error` of kind `ErrorKind::InvalidMasterKey` for the one in 2^127 seeds that
+    /// BIP32 can't derive a master key from.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bip39::{Mnemonic, Language, Seed};
+    ///
+    /// let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+    /// let mnemonic = Mnemonic::from_phrase(phrase, Language::English).unwrap();
+    /// let seed = Seed::new(&mnemonic, "");
+    ///
+    /// assert_eq!(seed.fingerprint().unwrap(), [0x73, 0xc5, 0xda, 0x0a]);
+    /// ```
+    pub fn fingerprint(&self) -> Result<[u8; FINGERPRINT_BYTES], Error> {
+        Ok(fingerprint(self.as_bytes())?)
+    }
+
+    /// Get the fingerprint as three words of the word list, easier to read out and compare
+    ///
+    /// The 32 bits of the fingerprint are followed by a `0` bit and split into 11-bit words, the
+    /// same way the words of a mnemonic are.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bip39::{Mnemonic, Language, Seed};
+    ///
+    /// let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+    /// let mnemonic = Mnemonic::from_phrase(phrase, Language::English).unwrap();
+    /// let seed = Seed::new(&mnemonic, "");
+    ///
+    /// assert_eq!(seed.fingerprint_words(Language::English).unwrap(), "inherit conduct little");
+    /// ```
+    pub fn fingerprint_words(&self, lang: Language) -> Result<String, Error> {
+        fingerprint_words(fingerprint(self.as_bytes())?, lang)
+    }
+}
+
+fn fingerprint(seed: &[u8]) -> Result<[u8; FINGERPRINT_BYTES], ErrorKind> {
+    let mut mac = Hmac::<Sha512>::new_from_slice(MASTER_KEY_SALT).expect("any key size works");
+
+    mac.update(seed);
+
+    let mut master = mac.finalize().into_bytes();
+    // The left half is the master private key, the right half the chain code
+    let key = SecretKey::from_slice(&master[..32]);
+
+    master.as_mut_slice().zeroize();
+
+    let public_key = key
+        .map_err(|_| ErrorKind::InvalidMasterKey)?
+        .public_key()
+        .to_encoded_point(true);
+    let hash = Ripemd160::digest(Sha256::digest(public_key.as_bytes()));
+
+    let mut fingerprint = [0; FINGERPRINT_BYTES];
+
+    fingerprint.copy_from_slice(&hash[..FINGERPRINT_BYTES]);
+
+    Ok(fingerprint)
+}
+
+fn fingerprint_words(
+    fingerprint: [u8; FINGERPRINT_BYTES],
+    lang: Language,
+) -> Result<String, Error> {
+    let wordlist = lang.try_wordlist()?;
+    let bits = u32::from_be_bytes(fingerprint);
+
+    Ok([bits >> 21, bits >> 10, bits << 1]
+        .iter()
+        .map(|&word| wordlist.get_word(Bits11::from((word & 0x7FF) as u16)))
+        .collect::<Vec<_>>()
+        .join(" "))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::mnemonic::Mnemonic;
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn bip32_test_vector_1() {
+        let seed: Vec<u8> = (0..16).collect();
+
+        assert_eq!(fingerprint(&seed).unwrap(), [0x34, 0x42, 0x19, 0x3e]);
+        assert_eq!(
+            fingerprint_words([0x34, 0x42, 0x19, 0x3e], Language::English).unwrap(),
+            "crowd awkward exhibit"
+        );
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn passphrase_changes_fingerprint() {
+        let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let mnemonic = Mnemonic::from_phrase(phrase, Language::English).unwrap();
+
+        assert_eq!(
+            Seed::new(&mnemonic, "").fingerprint().unwrap(),
+            [0x73, 0xc5, 0xda, 0x0a]
+        );
+        assert_ne!(
+            Seed::new(&mnemonic, "TREZOR").fingerprint().unwrap(),
+            [0x73, 0xc5, 0xda, 0x0a]
+        );
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn fingerprint_words_cover_all_bits() {
+        assert_eq!(
+            fingerprint_words([0; 4], Language::English).unwrap(),
+            "abandon abandon abandon"
+        );
+        assert_eq!(
+            fingerprint_words([0xff; 4], Language::English).unwrap(),
+            "zoo zoo zone"
+        );
+    }
+}
@@ -0,0 +1,699 @@
+//! Password protected storage for a [`Mnemonic`][Mnemonic] at rest.
+//!
+//! The password is stretched with scrypt or Argon2id into a key for XChaCha20-Poly1305, which
+//! encrypts the mnemonic entropy. Everything needed to decrypt it again, apart from the password,
+//! is kept in a plaintext header that is authenticated along with the ciphertext, so tampering
+//! with any byte of the file makes decryption fail.
+//!
+//! ```
+//! use bip39::{Mnemonic, Language};
+//! use bip39::keystore::{Keystore, Kdf};
+//!
+//! let mnemonic = Mnemonic::from_phrase(
+//!     "crop cash unable insane eight faith inflict route frame loud box vibrant",
+//!     Language::English,
+//! ).unwrap();
+//!
+//! // Cheap parameters to keep the example fast, use `Kdf::default()` in practice
+//! let kdf = Kdf::Argon2id { m_cost: 64, t_cost: 1, p_cost: 1 };
+//! let bytes = Keystore::encrypt(&mnemonic, "hunter2", kdf, b"savings").unwrap().to_bytes();
+//!
+//! let keystore = Keystore::from_bytes(&bytes).unwrap();
+//!
+//! assert_eq!(keystore.metadata(), b"savings");
+//! assert!(keystore.needs_upgrade(&Kdf::default()));
+//! assert_eq!(keystore.decrypt("hunter2").unwrap(), mnemonic);
+//! assert!(keystore.decrypt("hunter3").is_err());
+//! ```
+//!
+//! ## Format
+//!
+//! All integers are big-endian.
+//!
+//! | Field      | Size        | Contents                                            |
+//! |------------|-------------|-----------------------------------------------------|
+//! | magic      | 4           | `B39K`                                              |
+//! | version    | 1           | `1`                                                 |
+//! | kdf        | 1           | `1` for scrypt, `2` for Argon2id (version `0x13`)   |
+//! | kdf params | 9 or 12     | scrypt: `log_n: u8, r: u32, p: u32`, Argon2id: `m_cost: u32, t_cost: u32, p_cost: u32` |
+//! | salt       | 16          | KDF salt                                            |
+//! | nonce      | 24          | XChaCha20-Poly1305 nonce                            |
+//! | language   | 1 + n       | length prefixed language code, e.g. `en`            |
+//! | metadata   | 2 + n       | length prefixed application data, *not encrypted*   |
+//! | ciphertext | entropy + 16 | encrypted entropy followed by the Poly1305 tag     |
+//!
+//! The KDF turns the NFKD normalized password and the salt into a 32 byte key. Everything before
+//! the ciphertext is passed to XChaCha20-Poly1305 as associated data.
+//!
+//! Keystores that ask the KDF for more than 4 GiB of memory, or for an scrypt `log_n` above 22,
+//! `r * p` above 64, Argon2id `t_cost` above 64 or `p_cost` above 16, are rejected with
+//! `ErrorKind::InvalidKeystore` before any key is derived, and can't be created either.
+//!
+//! ## Test vectors
+//!
+//! The phrase `crop cash unable insane eight faith inflict route frame loud box vibrant`,
+//! encrypted with the password `hunter2` and the metadata `test`, with sixteen `0x11` bytes as
+//! the salt and twenty four `0x22` bytes as the nonce. `tests/keystore_vectors.py` in the
+//! repository generates both with Python's `hashlib` and the `cryptography` package, without
+//! using this crate.
+//!
+//! scrypt with `log_n = 10, r = 8, p = 1`:
+//!
+//! ```text
+//! 4233394b01010a00000008000000011111111111111111111111111111111122222222222222222222222222222222
+//! 222222222222222202656e00047465737443ee57413af448d2dee0bf6f471387cf99d25431f6e23435a06425f38b
+//! c80421
+//! ```
+//!
+//! Argon2id with `m_cost = 64, t_cost = 1, p_cost = 1`:
+//!
+//! ```text
+//! 4233394b01020000004000000001000000011111111111111111111111111111111122222222222222222222222222
+//! 222222222222222222222202656e000474657374c59268d5dd8f83eebefba8069a30584c2b5b6f7a57d192538aa379
+//! 8508a90cf2
+//! ```
+//!
+//! [Mnemonic]: ../mnemonic/struct.Mnemonic.html
+
+use crate::error::ErrorKind;
+use crate::language::Language;
+use crate::mnemonic::Mnemonic;
+use anyhow::Error;
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+use rand::{thread_rng, RngCore};
+use unicode_normalization::UnicodeNormalization;
+use zeroize::Zeroizing;
+
+/// Leading bytes of every keystore
+pub const MAGIC: [u8; 4] = *b"B39K";
+
+/// Format version written by this crate
+pub const VERSION: u8 = 1;
+
+const KDF_SCRYPT: u8 = 1;
+const KDF_ARGON2ID: u8 = 2;
+const SALT_BYTES: usize = 16;
+const NONCE_BYTES: usize = 24;
+const KEY_BYTES: usize = 32;
+const TAG_BYTES: usize = 16;
+
+/// Most memory a keystore may ask the KDF for, 4 GiB
+const MAX_KDF_MEMORY: u64 = 4 << 30;
+/// Highest accepted scrypt `log_n`
+const MAX_SCRYPT_LOG_N: u8 = 22;
+/// Highest accepted scrypt `r * p`
+const MAX_SCRYPT_RP: u64 = 64;
+/// Highest accepted Argon2id `t_cost`
+const MAX_ARGON2_T_COST: u32 = 64;
+/// Highest accepted Argon2id `p_cost`
+const MAX_ARGON2_P_COST: u32 = 16;
+
+/// Key derivation function and its cost parameters
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kdf {
+    /// scrypt with `N = 2^log_n`
+    Scrypt { log_n: u8, r: u32, p: u32 },
+    /// Argon2id with `m_cost` in KiB
+    Argon2id {
+        m_cost: u32,
+        t_cost: u32,
+        p_cost: u32,
+    },
+}
+
+impl Default for Kdf {
+    /// Argon2id with 64 MiB of memory and 3 passes
+    fn default() -> Self {
+        Kdf::Argon2id {
+            m_cost: 64 * 1024,
+            t_cost: 3,
+            p_cost: 1,
+        }
+    }
+}
+
+impl Kdf {
+    /// Reject parameters that would take more than 4 GiB of memory or an unreasonable time, so a
+    /// corrupted or hostile keystore can't exhaust the machine that opens it
+    fn check_bounds(&self) -> Result<(), ErrorKind> {
+        let in_bounds = match *self {
+            Kdf::Scrypt { log_n, r, p } => {
+                log_n <= MAX_SCRYPT_LOG_N
+                    && r as u64 * p as u64 <= MAX_SCRYPT_RP
+                    && 128 * r as u64 * (1 << log_n) <= MAX_KDF_MEMORY
+            }
+            Kdf::Argon2id {
+                m_cost,
+                t_cost,
+                p_cost,
+            } => {
+                m_cost as u64 * 1024 <= MAX_KDF_MEMORY
+                    && t_cost <= MAX_ARGON2_T_COST
+                    && p_cost <= MAX_ARGON2_P_COST
+            }
+        };
+
+        if !in_bounds {
+            return Err(ErrorKind::InvalidKeystore("kdf parameters too large"));
+        }
+
+        Ok(())
+    }
+
+    fn derive_key(&self, password: &str, salt: &[u8]) -> Result<Zeroizing<[u8; KEY_BYTES]>, Error> {
+        let password: Zeroizing<String> = Zeroizing::new(password.nfkd().collect());
+        let mut key = Zeroizing::new([0; KEY_BYTES]);
+
+        match *self {
+            Kdf::Scrypt { log_n, r, p } => {
+                let params = scrypt::Params::new(log_n, r, p, KEY_BYTES)
+                    .map_err(|_| ErrorKind::InvalidKeystore("invalid scrypt parameters"))?;
+
+                scrypt::scrypt(password.as_bytes(), salt, &params, &mut *key)
+                    .map_err(|_| ErrorKind::InvalidKeystore("invalid scrypt parameters"))?;
+            }
+            Kdf::Argon2id {
+                m_cost,
+                t_cost,
+                p_cost,
+            } => {
+                let params = argon2::Params::new(m_cost, t_cost, p_cost, Some(KEY_BYTES))
+                    .map_err(|_| ErrorKind::InvalidKeystore("invalid Argon2id parameters"))?;
+
+                argon2::Argon2::new(argon2::Algorithm::Argon2id, argon2::Version::V0x13, params)
+                    .hash_password_into(password.as_bytes(), salt, &mut *key)
+                    .map_err(|_| ErrorKind::InvalidKeystore("invalid Argon2id parameters"))?;
+            }
+        }
+
+        Ok(key)
+    }
+
+    /// Whether `self` costs less than `other` on any parameter, or is a different function
+    fn is_weaker_than(&self, other: &Kdf) -> bool {
+        match (*self, *other) {
+            (
+                Kdf::Scrypt { log_n, r, p },
+                Kdf::Scrypt {
+                    log_n: log_n2,
+                    r: r2,
+                    p: p2,
+                },
+            ) => log_n < log_n2 || r < r2 || p < p2,
+            (
+                Kdf::Argon2id {
+                    m_cost,
+                    t_cost,
+                    p_cost,
+                },
+                Kdf::Argon2id {
+                    m_cost: m_cost2,
+                    t_cost: t_cost2,
+                    p_cost: p_cost2,
+                },
+            ) => m_cost < m_cost2 || t_cost < t_cost2 || p_cost < p_cost2,
+            _ => true,
+        }
+    }
+}
+
+/// An encrypted [`Mnemonic`][Mnemonic], see the [module documentation](./index.html) for the
+/// byte format.
+///
+/// [Mnemonic]: ../mnemonic/struct.Mnemonic.html
+#[derive(Debug, Clone, PartialEq)]
+pub struct Keystore {
+    version: u8,
+    kdf: Kdf,
+    salt: [u8; SALT_BYTES],
+    nonce: [u8; NONCE_BYTES],
+    lang: Language,
+    metadata: Vec<u8>,
+    ciphertext: Vec<u8>,
+}
+
+impl Keystore {
+    /// Encrypt a [`Mnemonic`][Mnemonic] with a password, using a fresh random salt and nonce
+    ///
+    /// `metadata` is stored alongside the mnemonic. It is authenticated but **not** encrypted,
+    /// so it can be read with [`Keystore::metadata()`][Keystore::metadata()] without the password.
+    ///
+    /// Mnemonics in a [custom language][custom] are rejected with an `Error` of kind
+    /// `ErrorKind::InvalidKeystore`, as their language code could not be read back.
+    ///
+    /// [Mnemonic]: ../mnemonic/struct.Mnemonic.html
+    /// [Keystore::metadata()]: ./struct.Keystore.html#method.metadata
+    /// [custom]: ../enum.Language.html#method.custom
+    pub fn encrypt(
+        mnemonic: &Mnemonic,
+        password: &str,
+        kdf: Kdf,
+        metadata: &[u8],
+    ) -> Result<Keystore, Error> {
+        let mut salt = [0; SALT_BYTES];
+        let mut nonce = [0; NONCE_BYTES];
+
+        thread_rng().fill_bytes(&mut salt);
+        thread_rng().fill_bytes(&mut nonce);
+
+        Keystore::encrypt_with(mnemonic, password, kdf, metadata, salt, nonce)
+    }
+
+    fn encrypt_with(
+        mnemonic: &Mnemonic,
+        password: &str,
+        kdf: Kdf,
+        metadata: &[u8],
+        salt: [u8; SALT_BYTES],
+        nonce: [u8; NONCE_BYTES],
+    ) -> Result<Keystore, Error> {
+        if metadata.len() > u16::MAX as usize {
+            Err(ErrorKind::InvalidKeystore("metadata too long"))?;
+        }
+
+        kdf.check_bounds()?;
+
+        if let Language::Custom(_) = mnemonic.language() {
+            Err(ErrorKind::InvalidKeystore("custom languages can't be stored"))?;
+        }
+
+        let mut keystore = Keystore {
+            version: VERSION,
+            kdf,
+            salt,
+            nonce,
+            lang: mnemonic.language(),
+            metadata: metadata.to_vec(),
+            ciphertext: Vec::new(),
+        };
+
+        let key = kdf.derive_key(password, &salt)?;
+        let header = keystore.header();
+
+        keystore.ciphertext = XChaCha20Poly1305::new(Key::from_slice(&*key))
+            .encrypt(
+                XNonce::from_slice(&nonce),
+                Payload {
+                    msg: mnemonic.entropy(),
+                    aad: &header,
+                },
+            )
+            .map_err(|_| ErrorKind::InvalidKeystore("encryption failed"))?;
+
+        Ok(keystore)
+    }
+
+    /// Parse a keystore from bytes, without decrypting it
+    pub fn from_bytes(bytes: &[u8]) -> Result<Keystore, Error> {
+        let mut reader = Reader(bytes);
+
+        if reader.take(MAGIC.len())? != MAGIC {
+            Err(ErrorKind::InvalidKeystore("not a keystore"))?;
+        }
+
+        let version = reader.u8()?;
+
+        if version != VERSION {
+            Err(ErrorKind::InvalidKeystore("unsupported version"))?;
+        }
+
+        let kdf = match reader.u8()? {
+            KDF_SCRYPT => Kdf::Scrypt {
+                log_n: reader.u8()?,
+                r: reader.u32()?,
+                p: reader.u32()?,
+            },
+            KDF_ARGON2ID => Kdf::Argon2id {
+                m_cost: reader.u32()?,
+                t_cost: reader.u32()?,
+                p_cost: reader.u32()?,
+            },
+            _ => Err(ErrorKind::InvalidKeystore(
+                "unsupported key derivation function",
+            ))?,
+        };
+
+        kdf.check_bounds()?;
+
+        let mut salt = [0; SALT_BYTES];
+        let mut nonce = [0; NONCE_BYTES];
+
+        salt.copy_from_slice(reader.take(SALT_BYTES)?);
+        nonce.copy_from_slice(reader.take(NONCE_BYTES)?);
+
+        let len = reader.u8()? as usize;
+        let lang = std::str::from_utf8(reader.take(len)?)
+            .ok()
+            .and_then(Language::from_language_code)
+            .ok_or(ErrorKind::InvalidKeystore("unsupported language"))?;
+
+        let len = reader.u16()? as usize;
+        let metadata = reader.take(len)?.to_vec();
+
+        if reader.0.len() < TAG_BYTES {
+            Err(ErrorKind::InvalidKeystore("unexpected end of data"))?;
+        }
+
+        Ok(Keystore {
+            version,
+            kdf,
+            salt,
+            nonce,
+            lang,
+            metadata,
+            ciphertext: reader.0.to_vec(),
+        })
+    }
+
+    /// Serialize the keystore
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.header();
+
+        bytes.extend_from_slice(&self.ciphertext);
+        bytes
+    }
+
+    /// Decrypt the [`Mnemonic`][Mnemonic]
+    ///
+    /// A wrong password and a corrupted or tampered keystore are indistinguishable, both return
+    /// an `Error` of kind `ErrorKind::KeystoreDecryption`.
+    ///
+    /// [Mnemonic]: ../mnemonic/struct.Mnemonic.html
+    pub fn decrypt(&self, password: &str) -> Result<Mnemonic, Error> {
+        let key = self.kdf.derive_key(password, &self.salt)?;
+
+        let entropy = XChaCha20Poly1305::new(Key::from_slice(&*key))
+            .decrypt(
+                XNonce::from_slice(&self.nonce),
+                Payload {
+                    msg: &self.ciphertext,
+                    aad: &self.header(),
+                },
+            )
+            .map_err(|_| ErrorKind::KeystoreDecryption)?;
+
+        Mnemonic::from_entropy(&Zeroizing::new(entropy), self.lang)
+    }
+
+    /// Format version of the keystore
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+
+    /// Key derivation function and parameters protecting the keystore
+    pub fn kdf(&self) -> Kdf {
+        self.kdf
+    }
+
+    /// Language of the stored [`Mnemonic`][Mnemonic]
+    ///
+    /// [Mnemonic]: ../mnemonic/struct.Mnemonic.html
+    pub fn language(&self) -> Language {
+        self.lang
+    }
+
+    /// Application data stored in the clear alongside the [`Mnemonic`][Mnemonic]
+    ///
+    /// [Mnemonic]: ../mnemonic/struct.Mnemonic.html
+    pub fn metadata(&self) -> &[u8] {
+        &self.metadata
+    }
+
+    /// Whether the keystore uses an older format version, or a key derivation function that is
+    /// weaker than `kdf`
+    pub fn needs_upgrade(&self, kdf: &Kdf) -> bool {
+        self.version < VERSION || self.kdf.is_weaker_than(kdf)
+    }
+
+    /// Re-encrypt the keystore with the current format version and the given `kdf`, keeping the
+    /// metadata. The salt and nonce are regenerated.
+    pub fn upgrade(&self, password: &str, kdf: Kdf) -> Result<Keystore, Error> {
+        let mnemonic = self.decrypt(password)?;
+
+        Keystore::encrypt(&mnemonic, password, kdf, &self.metadata)
+    }
+
+    fn header(&self) -> Vec<u8> {
+        let code = self.lang.language_code();
+        let mut header = Vec::with_capacity(80 + code.len() + self.metadata.len());
+
+        header.extend_from_slice(&MAGIC);
+        header.push(self.version);
+
+        match self.kdf {
+            Kdf::Scrypt { log_n, r, p } => {
+                header.push(KDF_SCRYPT);
+                header.push(log_n);
+                header.extend_from_slice(&r.to_be_bytes());
+                header.extend_from_slice(&p.to_be_bytes());
+            }
+            Kdf::Argon2id {
+                m_cost,
+                t_cost,
+                p_cost,
+            } => {
+                header.push(KDF_ARGON2ID);
+                header.extend_from_slice(&m_cost.to_be_bytes());
+                header.extend_from_slice(&t_cost.to_be_bytes());
+                header.extend_from_slice(&p_cost.to_be_bytes());
+            }
+        }
+
+        header.extend_from_slice(&self.salt);
+        header.extend_from_slice(&self.nonce);
+        header.push(code.len() as u8);
+        header.extend_from_slice(code.as_bytes());
+        header.extend_from_slice(&(self.metadata.len() as u16).to_be_bytes());
+        header.extend_from_slice(&self.metadata);
+        header
+    }
+}
+
+impl Mnemonic {
+    /// Encrypt the mnemonic with a password, using the default [`Kdf`][Kdf] and no metadata
+    ///
+    /// See the [`keystore`][keystore] module for the format and more options, and
+    /// [`Keystore::encrypt()`][Keystore::encrypt()] for the errors.
+    ///
+    /// [Kdf]: ./keystore/enum.Kdf.html
+    /// [keystore]: ./keystore/index.html
+    /// [Keystore::encrypt()]: ./keystore/struct.Keystore.html#method.encrypt
+    pub fn encrypt(&self, password: &str) -> Result<Vec<u8>, Error> {
+        Ok(Keystore::encrypt(self, password, Kdf::default(), &[])?.to_bytes())
+    }
+
+    /// Decrypt a mnemonic encrypted with [`Mnemonic::encrypt()`][Mnemonic::encrypt()] or
+    /// [`Keystore::encrypt()`][Keystore::encrypt()]
+    ///
+    /// [Mnemonic::encrypt()]: ./mnemonic/struct.Mnemonic.html#method.encrypt
+    /// [Keystore::encrypt()]: ./keystore/struct.Keystore.html#method.encrypt
+    pub fn decrypt(bytes: &[u8], password: &str) -> Result<Mnemonic, Error> {
+        Keystore::from_bytes(bytes)?.decrypt(password)
+    }
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], ErrorKind> {
+        if self.0.len() < len {
+            return Err(ErrorKind::InvalidKeystore("unexpected end of data"));
+        }
+
+        let (head, tail) = self.0.split_at(len);
+
+        self.0 = tail;
+        Ok(head)
+    }
+
+    fn u8(&mut self) -> Result<u8, ErrorKind> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, ErrorKind> {
+        let mut buf = [0; 2];
+
+        buf.copy_from_slice(self.take(2)?);
+        Ok(u16::from_be_bytes(buf))
+    }
+
+    fn u32(&mut self) -> Result<u32, ErrorKind> {
+        let mut buf = [0; 4];
+
+        buf.copy_from_slice(self.take(4)?);
+        Ok(u32::from_be_bytes(buf))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::wordlist::CustomWordList;
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    const PHRASE: &str = "crop cash unable insane eight faith inflict route frame loud box vibrant";
+    const SALT: [u8; SALT_BYTES] = [0x11; SALT_BYTES];
+    const NONCE: [u8; NONCE_BYTES] = [0x22; NONCE_BYTES];
+    const SCRYPT: Kdf = Kdf::Scrypt {
+        log_n: 10,
+        r: 8,
+        p: 1,
+    };
+    const ARGON2ID: Kdf = Kdf::Argon2id {
+        m_cost: 64,
+        t_cost: 1,
+        p_cost: 1,
+    };
+
+    // PHRASE encrypted with password "hunter2" and metadata "test", generated independently of
+    // this crate by tests/keystore_vectors.py
+    const SCRYPT_VECTOR: &str = "4233394b01010a00000008000000011111111111111111111111111111111122222222222222222222222222222222222222222222222202656e00047465737443ee57413af448d2dee0bf6f471387cf99d25431f6e23435a06425f38bc80421";
+    const ARGON2ID_VECTOR: &str = "4233394b01020000004000000001000000011111111111111111111111111111111122222222222222222222222222222222222222222222222202656e000474657374c59268d5dd8f83eebefba8069a30584c2b5b6f7a57d192538aa3798508a90cf2";
+
+    fn mnemonic() -> Mnemonic {
+        Mnemonic::from_phrase(PHRASE, Language::English).unwrap()
+    }
+
+    fn vector(kdf: Kdf) -> Vec<u8> {
+        Keystore::encrypt_with(&mnemonic(), "hunter2", kdf, b"test", SALT, NONCE)
+            .unwrap()
+            .to_bytes()
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn scrypt_vector() {
+        let bytes = vector(SCRYPT);
+
+        assert_eq!(hex::encode(&bytes), SCRYPT_VECTOR);
+        assert_eq!(Mnemonic::decrypt(&bytes, "hunter2").unwrap(), mnemonic());
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn argon2id_vector() {
+        let bytes = vector(ARGON2ID);
+
+        assert_eq!(hex::encode(&bytes), ARGON2ID_VECTOR);
+        assert_eq!(Mnemonic::decrypt(&bytes, "hunter2").unwrap(), mnemonic());
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn encrypt_round_trip() {
+        let bytes = mnemonic().encrypt("hunter2").unwrap();
+        let keystore = Keystore::from_bytes(&bytes).unwrap();
+
+        assert_eq!(keystore.version(), VERSION);
+        assert_eq!(keystore.kdf(), Kdf::default());
+        assert_eq!(keystore.language(), Language::English);
+        assert_eq!(keystore.metadata(), b"");
+        assert_eq!(Mnemonic::decrypt(&bytes, "hunter2").unwrap(), mnemonic());
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn wrong_password() {
+        let bytes = vector(ARGON2ID);
+
+        assert!(Mnemonic::decrypt(&bytes, "hunter3").is_err());
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn password_is_normalized() {
+        let bytes = Keystore::encrypt(&mnemonic(), "caf\u{e9}", ARGON2ID, &[])
+            .unwrap()
+            .to_bytes();
+
+        assert!(Mnemonic::decrypt(&bytes, "cafe\u{301}").is_ok());
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn header_is_authenticated() {
+        let bytes = vector(ARGON2ID);
+
+        for idx in 0..bytes.len() {
+            let mut tampered = bytes.clone();
+
+            tampered[idx] ^= 1;
+            assert!(Mnemonic::decrypt(&tampered, "hunter2").is_err(), "{}", idx);
+        }
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn malformed() {
+        let bytes = vector(SCRYPT);
+
+        for len in 0..bytes.len() - TAG_BYTES {
+            assert!(Keystore::from_bytes(&bytes[..len]).is_err(), "{}", len);
+        }
+
+        let mut version = bytes.clone();
+        version[4] = 2;
+        assert!(Keystore::from_bytes(&version).is_err());
+
+        let mut kdf = bytes;
+        kdf[5] = 3;
+        assert!(Keystore::from_bytes(&kdf).is_err());
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn kdf_parameters_too_large() {
+        let mut m_cost = vector(ARGON2ID);
+        m_cost[6..10].copy_from_slice(&u32::MAX.to_be_bytes());
+        assert!(Keystore::from_bytes(&m_cost).is_err());
+        assert!(Mnemonic::decrypt(&m_cost, "hunter2").is_err());
+
+        let mut log_n = vector(SCRYPT);
+        log_n[6] = 255;
+        assert!(Keystore::from_bytes(&log_n).is_err());
+
+        let mut rp = vector(SCRYPT);
+        rp[11..15].copy_from_slice(&1024u32.to_be_bytes());
+        assert!(Keystore::from_bytes(&rp).is_err());
+
+        let kdf = Kdf::Argon2id {
+            m_cost: (4 << 20) + 1,
+            t_cost: 1,
+            p_cost: 1,
+        };
+        assert!(Keystore::encrypt(&mnemonic(), "hunter2", kdf, &[]).is_err());
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn custom_language_rejected() {
+        let words: Vec<String> = (0..2048).map(|idx| format!("w{:04}", idx)).collect();
+        let list = CustomWordList::new("x-keystore", &words.join(" ")).unwrap();
+        let mnemonic = Mnemonic::from_entropy(&[0; 16], Language::custom(list).unwrap()).unwrap();
+
+        assert!(Keystore::encrypt(&mnemonic, "hunter2", ARGON2ID, &[]).is_err());
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn upgrade() {
+        let keystore = Keystore::from_bytes(&vector(SCRYPT)).unwrap();
+
+        assert!(keystore.needs_upgrade(&ARGON2ID));
+        assert!(!keystore.needs_upgrade(&SCRYPT));
+        assert!(keystore.needs_upgrade(&Kdf::Scrypt {
+            log_n: 11,
+            r: 8,
+            p: 1,
+        }));
+
+        let upgraded = keystore.upgrade("hunter2", ARGON2ID).unwrap();
+
+        assert!(!upgraded.needs_upgrade(&ARGON2ID));
+        assert_eq!(upgraded.metadata(), b"test");
+        assert_eq!(upgraded.decrypt("hunter2").unwrap(), mnemonic());
+        assert!(keystore.upgrade("hunter3", ARGON2ID).is_err());
+    }
+}
@@ -1,103 +1,270 @@
+use crate::error::ErrorKind;
 use crate::util::{Bits, Bits11};
+use anyhow::Error;
 use rustc_hash::FxHashMap;
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::path::Path;
+#[cfg(feature = "verify-wordlists")]
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, PoisonError};
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
+use zeroize::Zeroize;
 
+/// Upper bound on the byte length of a word in any of the word lists, used to pad words to a
+/// fixed width for constant time comparison.
+const MAX_WORD_BYTES: usize = 64;
+
+/// Upper bound on the byte length of a custom language code, so it fits the length prefix of
+/// every format that stores it
+const MAX_CODE_BYTES: usize = 32;
+
+/// Every custom language created so far, so each code is leaked only once
+static CUSTOM_LANGUAGES: Mutex<Vec<&'static CustomLanguage>> = Mutex::new(Vec::new());
+
+/// Word -> index lookups for a [`WordList`][WordList], see [`Language::wordmap()`][wordmap]
+///
+/// [WordList]: ./struct.WordList.html
+/// [wordmap]: ../enum.Language.html#method.wordmap
 pub struct WordMap {
-    inner: FxHashMap<&'static str, Bits11>,
+    inner: Lookup,
+}
+
+enum Lookup {
+    /// Perfect hash map generated at build time for the built in languages
+    #[cfg(not(feature = "compressed-wordlists"))]
+    Static(&'static phf::Map<&'static str, u16>),
+    /// Built at runtime for custom and compressed word lists
+    Dynamic(FxHashMap<&'static str, Bits11>),
 }
 
+/// The 2048 words of a language, in the order that gives each word its 11-bit value
+///
+/// Words are stored in NFKD form, see [`Language::wordlist()`][wordlist].
+///
+/// [wordlist]: ../enum.Language.html#method.wordlist
 pub struct WordList {
-    inner: Vec<&'static str>,
+    inner: &'static [&'static str],
+    /// The same words sorted by byte order, for prefix search
+    sorted: &'static [&'static str],
+    /// Set once the list has been checked against its official hash
+    #[cfg(feature = "verify-wordlists")]
+    verified: AtomicBool,
 }
 
 impl WordMap {
+    /// Get the 11-bit value of a word, if it's in the list
     pub fn get_bits(&self, word: &str) -> Option<Bits11> {
-        self.inner.get(word).cloned()
+        match &self.inner {
+            #[cfg(not(feature = "compressed-wordlists"))]
+            Lookup::Static(map) => map.get(word).map(|&index| Bits11::from(index)),
+            Lookup::Dynamic(map) => map.get(word).cloned(),
+        }
+    }
+
+    /// Get the position of a word in the list, if it's in the list
+    pub fn get_index(&self, word: &str) -> Option<usize> {
+        self.get_bits(word).map(|bits| u16::from(bits) as usize)
+    }
+
+    /// Build a hash map for a word list that isn't known at compile time
+    fn from_wordlist(wordlist: &WordList) -> WordMap {
+        let inner = wordlist
+            .iter()
+            .enumerate()
+            .map(|(i, word)| (word, Bits11::from(i as u16)))
+            .collect();
+
+        WordMap {
+            inner: Lookup::Dynamic(inner),
+        }
     }
 }
 
 impl WordList {
+    /// Build a word list that isn't known at compile time, the words and the sorted copy are
+    /// leaked as they live for the rest of the program
+    fn from_words(words: Vec<&'static str>) -> WordList {
+        let mut sorted = words.clone();
+        sorted.sort_unstable();
+
+        WordList {
+            inner: Box::leak(words.into_boxed_slice()),
+            sorted: Box::leak(sorted.into_boxed_slice()),
+            #[cfg(feature = "verify-wordlists")]
+            verified: AtomicBool::new(false),
+        }
+    }
+
+    /// Word list from the static arrays generated by `build.rs`
+    #[cfg(not(feature = "compressed-wordlists"))]
+    const fn from_static(
+        inner: &'static [&'static str],
+        sorted: &'static [&'static str],
+    ) -> WordList {
+        WordList {
+            inner,
+            sorted,
+            #[cfg(feature = "verify-wordlists")]
+            verified: AtomicBool::new(false),
+        }
+    }
+
+    /// Unpack a front coded word list generated by `build.rs`
+    ///
+    /// `order` has the list position of each sorted word, or is empty if the list is sorted.
+    #[cfg(feature = "compressed-wordlists")]
+    fn unpack(packed: &[u8], order: &[u16]) -> WordList {
+        let mut text = Vec::with_capacity(packed.len() * 2);
+        let mut ends = Vec::with_capacity(2048);
+        let mut previous = 0..0;
+        let mut pos = 0;
+
+        while pos < packed.len() {
+            let shared = packed[pos] as usize;
+            let suffix = packed[pos + 1] as usize;
+            let start = text.len();
+
+            text.extend_from_within(previous.start..previous.start + shared);
+            text.extend_from_slice(&packed[pos + 2..pos + 2 + suffix]);
+            previous = start..text.len();
+            ends.push(text.len());
+            pos += 2 + suffix;
+        }
+
+        let text = String::from_utf8(text).expect("packed at build time from valid UTF-8");
+        let text: &'static str = Box::leak(text.into_boxed_str());
+        let mut start = 0;
+        let sorted: Vec<&'static str> = ends
+            .iter()
+            .map(|&end| {
+                let word = &text[start..end];
+                start = end;
+                word
+            })
+            .collect();
+
+        if order.is_empty() {
+            return WordList::from_words(sorted);
+        }
+
+        let mut words = vec![""; sorted.len()];
+
+        for (word, &index) in sorted.iter().zip(order) {
+            words[index as usize] = word;
+        }
+
+        WordList::from_words(words)
+    }
+
+    /// Get the word with the given 11-bit value
     pub fn get_word(&self, bits: Bits11) -> &'static str {
         self.inner[bits.bits() as usize]
     }
 
-    pub fn get_words_by_prefix(&self, prefix: &str) -> &[&'static str] {
-        let start = self.inner
-            .binary_search(&prefix)
-            .unwrap_or_else(|idx| idx);
-        let count = self.inner[start..].iter()
-            .take_while(|word| word.starts_with(prefix))
-            .count();
+    /// Number of words in the list, always 2048 for the built in languages
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
 
-        &self.inner[start..start + count]
+    /// Whether the list has no words
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
     }
-}
 
-mod lazy {
-    use super::{Bits11, WordList, WordMap};
-    use once_cell::sync::Lazy;
+    /// Iterate over the words in list order
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = &'static str> + '_ {
+        self.inner.iter().copied()
+    }
 
-    /// lazy generation of the word list
-    fn gen_wordlist(lang_words: &'static str) -> WordList {
-        let inner: Vec<_> = lang_words.split_whitespace().collect();
+    /// Get the word at the given position, or `None` if it's out of range
+    pub fn get(&self, index: usize) -> Option<&'static str> {
+        self.inner.get(index).copied()
+    }
 
-        debug_assert!(inner.len() == 2048, "Invalid wordlist length");
+    /// Get the position of a word in the list
+    ///
+    /// This scans the list, use [`WordMap::get_index()`][get_index] for repeated lookups.
+    ///
+    /// [get_index]: ./struct.WordMap.html#method.get_index
+    pub fn index_of(&self, word: &str) -> Option<usize> {
+        self.inner.iter().position(|&candidate| candidate == word)
+    }
 
-        WordList { inner }
+    /// Whether the word is in the list
+    pub fn contains(&self, word: &str) -> bool {
+        self.index_of(word).is_some()
     }
 
-    /// lazy generation of the word map
-    fn gen_wordmap(wordlist: &WordList) -> WordMap {
-        let inner = wordlist
-            .inner
-            .iter()
-            .enumerate()
-            .map(|(i, item)| (*item, Bits11::from(i as u16)))
-            .collect();
+    /// Smallest number of characters that identifies every word in the list
+    ///
+    /// Words shorter than that are identified by the whole word. Characters are counted in the
+    /// NFKD form the list is stored in, so this is 4 for English, but larger for lists whose
+    /// characters decompose, like Korean.
+    pub fn unique_prefix_len(&self) -> usize {
+        unique_prefix_len(self.sorted)
+    }
+
+    /// Look up a word by comparing it against every entry in the list, taking the same time
+    /// regardless of which word it is, or whether it's in the list at all.
+    pub(crate) fn get_bits_ct(&self, word: &str) -> CtOption<Bits11> {
+        let mut padded = [0u8; MAX_WORD_BYTES];
+        let fits = word.len() <= MAX_WORD_BYTES;
+
+        if fits {
+            padded[..word.len()].copy_from_slice(word.as_bytes());
+        }
+
+        let mut found = Choice::from(0);
+        let mut index = 0u16;
+
+        for (idx, candidate) in self.inner.iter().enumerate() {
+            let mut padded_candidate = [0u8; MAX_WORD_BYTES];
+            padded_candidate[..candidate.len()].copy_from_slice(candidate.as_bytes());
+
+            let is_match = padded.ct_eq(&padded_candidate)
+                & (word.len() as u32).ct_eq(&(candidate.len() as u32));
 
-        WordMap { inner }
+            index.conditional_assign(&(idx as u16), is_match);
+            found |= is_match;
+        }
+
+        padded.zeroize();
+
+        CtOption::new(Bits11::from(index), found & Choice::from(fits as u8))
     }
 
-    pub static WORDLIST_ENGLISH: Lazy<WordList> =
-        Lazy::new(|| gen_wordlist(include_str!("langs/english.txt")));
-    #[cfg(feature = "chinese-simplified")]
-    pub static WORDLIST_CHINESE_SIMPLIFIED: Lazy<WordList> =
-        Lazy::new(|| gen_wordlist(include_str!("langs/chinese_simplified.txt")));
-    #[cfg(feature = "chinese-traditional")]
-    pub static WORDLIST_CHINESE_TRADITIONAL: Lazy<WordList> =
-        Lazy::new(|| gen_wordlist(include_str!("langs/chinese_traditional.txt")));
-    #[cfg(feature = "french")]
-    pub static WORDLIST_FRENCH: Lazy<WordList> =
-        Lazy::new(|| gen_wordlist(include_str!("langs/french.txt")));
-    #[cfg(feature = "italian")]
-    pub static WORDLIST_ITALIAN: Lazy<WordList> =
-        Lazy::new(|| gen_wordlist(include_str!("langs/italian.txt")));
-    #[cfg(feature = "japanese")]
-    pub static WORDLIST_JAPANESE: Lazy<WordList> =
-        Lazy::new(|| gen_wordlist(include_str!("langs/japanese.txt")));
-    #[cfg(feature = "korean")]
-    pub static WORDLIST_KOREAN: Lazy<WordList> =
-        Lazy::new(|| gen_wordlist(include_str!("langs/korean.txt")));
-    #[cfg(feature = "spanish")]
-    pub static WORDLIST_SPANISH: Lazy<WordList> =
-        Lazy::new(|| gen_wordlist(include_str!("langs/spanish.txt")));
+    /// Get all words starting with `prefix`, sorted by byte order
+    ///
+    /// The list order of Chinese, Japanese and some of the other lists isn't alphabetical, so the
+    /// search runs on a sorted copy of the list. Use [`WordMap::get_index()`][get_index] to get
+    /// the position of a result.
+    ///
+    /// [get_index]: ./struct.WordMap.html#method.get_index
+    pub fn get_words_by_prefix(&self, prefix: &str) -> &[&'static str] {
+        let start = self.sorted
+            .binary_search(&prefix)
+            .unwrap_or_else(|idx| idx);
+        let count = self.sorted[start..].iter()
+            .take_while(|word| word.starts_with(prefix))
+            .count();
 
-    pub static WORDMAP_ENGLISH: Lazy<WordMap> = Lazy::new(|| gen_wordmap(&WORDLIST_ENGLISH));
-    #[cfg(feature = "chinese-simplified")]
-    pub static WORDMAP_CHINESE_SIMPLIFIED: Lazy<WordMap> =
-        Lazy::new(|| gen_wordmap(&WORDLIST_CHINESE_SIMPLIFIED));
-    #[cfg(feature = "chinese-traditional")]
-    pub static WORDMAP_CHINESE_TRADITIONAL: Lazy<WordMap> =
-        Lazy::new(|| gen_wordmap(&WORDLIST_CHINESE_TRADITIONAL));
-    #[cfg(feature = "french")]
-    pub static WORDMAP_FRENCH: Lazy<WordMap> = Lazy::new(|| gen_wordmap(&WORDLIST_FRENCH));
-    #[cfg(feature = "italian")]
-    pub static WORDMAP_ITALIAN: Lazy<WordMap> = Lazy::new(|| gen_wordmap(&WORDLIST_ITALIAN));
-    #[cfg(feature = "japanese")]
-    pub static WORDMAP_JAPANESE: Lazy<WordMap> = Lazy::new(|| gen_wordmap(&WORDLIST_JAPANESE));
-    #[cfg(feature = "korean")]
-    pub static WORDMAP_KOREAN: Lazy<WordMap> = Lazy::new(|| gen_wordmap(&WORDLIST_KOREAN));
-    #[cfg(feature = "spanish")]
-    pub static WORDMAP_SPANISH: Lazy<WordMap> = Lazy::new(|| gen_wordmap(&WORDLIST_SPANISH));
+        &self.sorted[start..start + count]
+    }
+}
+
+/// Word lists and word maps generated by `build.rs` from `src/langs/*.txt`
+///
+/// By default these are static arrays and perfect hash maps. With the `compressed-wordlists`
+/// feature they are front coded and unpacked on first use instead.
+mod statics {
+    #[cfg(not(feature = "compressed-wordlists"))]
+    use super::Lookup;
+    use super::{WordList, WordMap};
+    #[cfg(feature = "compressed-wordlists")]
+    use once_cell::sync::Lazy;
+
+    include!(concat!(env!("OUT_DIR"), "/wordlists.rs"));
 }
 
 /// The language determines which words will be used in a mnemonic phrase, but also indirectly
@@ -108,13 +275,16 @@ mod lazy {
 ///
 /// [Mnemonic]: ./mnemonic/struct.Mnemonic.html
 /// [Seed]: ./seed/struct.Seed.html
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
 pub enum Language {
+    #[default]
     English,
     #[cfg(feature = "chinese-simplified")]
     ChineseSimplified,
     #[cfg(feature = "chinese-traditional")]
     ChineseTraditional,
+    #[cfg(feature = "czech")]
+    Czech,
     #[cfg(feature = "french")]
     French,
     #[cfg(feature = "italian")]
@@ -123,8 +293,14 @@ pub enum Language {
     Japanese,
     #[cfg(feature = "korean")]
     Korean,
+    #[cfg(feature = "portuguese")]
+    Portuguese,
     #[cfg(feature = "spanish")]
     Spanish,
+    /// A word list loaded at runtime, see [`Language::custom()`][custom]
+    ///
+    /// [custom]: ./enum.Language.html#method.custom
+    Custom(&'static CustomLanguage),
 }
 
 impl Language {
@@ -137,6 +313,8 @@ impl Language {
             "zh-hans" => Some(Language::ChineseSimplified),
             #[cfg(feature = "chinese-traditional")]
             "zh-hant" => Some(Language::ChineseTraditional),
+            #[cfg(feature = "czech")]
+            "cs" => Some(Language::Czech),
             #[cfg(feature = "french")]
             "fr" => Some(Language::French),
             #[cfg(feature = "italian")]
@@ -145,30 +323,92 @@ impl Language {
             "ja" => Some(Language::Japanese),
             #[cfg(feature = "korean")]
             "ko" => Some(Language::Korean),
+            #[cfg(feature = "portuguese")]
+            "pt" => Some(Language::Portuguese),
             #[cfg(feature = "spanish")]
             "es" => Some(Language::Spanish),
             _ => None,
         }
     }
 
-    /// Get the word list for this language
+    /// Get the language code for this language, the inverse of
+    /// [`Language::from_language_code()`][Language::from_language_code()]
+    ///
+    /// [Language::from_language_code()]: ./enum.Language.html#method.from_language_code
+    pub fn language_code(&self) -> &'static str {
+        match *self {
+            Language::English => "en",
+            #[cfg(feature = "chinese-simplified")]
+            Language::ChineseSimplified => "zh-hans",
+            #[cfg(feature = "chinese-traditional")]
+            Language::ChineseTraditional => "zh-hant",
+            #[cfg(feature = "czech")]
+            Language::Czech => "cs",
+            #[cfg(feature = "french")]
+            Language::French => "fr",
+            #[cfg(feature = "italian")]
+            Language::Italian => "it",
+            #[cfg(feature = "japanese")]
+            Language::Japanese => "ja",
+            #[cfg(feature = "korean")]
+            Language::Korean => "ko",
+            #[cfg(feature = "portuguese")]
+            Language::Portuguese => "pt",
+            #[cfg(feature = "spanish")]
+            Language::Spanish => "es",
+            Language::Custom(custom) => custom.code,
+        }
+    }
+
+    /// Get the word list for this language, see the [`wordlist`][wordlist] module
+    ///
+    /// With the `verify-wordlists` feature the first call for each built in language runs
+    /// [`Language::verify_wordlist()`][verify_wordlist].
+    ///
+    /// # Panics
+    ///
+    /// With the `verify-wordlists` feature, if the list was tampered with. Constructors that
+    /// return a `Result`, like [`Mnemonic::from_phrase()`][from_phrase], return an `Error` of kind
+    /// `ErrorKind::InvalidWordList` instead.
+    ///
+    /// [wordlist]: ./wordlist/index.html
+    /// [verify_wordlist]: ./enum.Language.html#method.verify_wordlist
+    /// [from_phrase]: ./mnemonic/struct.Mnemonic.html#method.from_phrase
     pub fn wordlist(&self) -> &'static WordList {
+        self.try_wordlist()
+            .unwrap_or_else(|err| panic!("{:?} {}", self, err))
+    }
+
+    /// Same as `wordlist`, returning the error when the list fails verification
+    pub(crate) fn try_wordlist(&self) -> Result<&'static WordList, Error> {
+        #[cfg(feature = "verify-wordlists")]
+        self.verify_on_first_use()?;
+
+        Ok(self.wordlist_unchecked())
+    }
+
+    fn wordlist_unchecked(&self) -> &'static WordList {
         match *self {
-            Language::English => &lazy::WORDLIST_ENGLISH,
+            Language::English => &statics::WORDLIST_ENGLISH,
             #[cfg(feature = "chinese-simplified")]
-            Language::ChineseSimplified => &lazy::WORDLIST_CHINESE_SIMPLIFIED,
+            Language::ChineseSimplified => &statics::WORDLIST_CHINESE_SIMPLIFIED,
             #[cfg(feature = "chinese-traditional")]
-            Language::ChineseTraditional => &lazy::WORDLIST_CHINESE_TRADITIONAL,
+            Language::ChineseTraditional => &statics::WORDLIST_CHINESE_TRADITIONAL,
+            #[cfg(feature = "czech")]
+            Language::Czech => &statics::WORDLIST_CZECH,
             #[cfg(feature = "french")]
-            Language::French => &lazy::WORDLIST_FRENCH,
+            Language::French => &statics::WORDLIST_FRENCH,
             #[cfg(feature = "italian")]
-            Language::Italian => &lazy::WORDLIST_ITALIAN,
+            Language::Italian => &statics::WORDLIST_ITALIAN,
             #[cfg(feature = "japanese")]
-            Language::Japanese => &lazy::WORDLIST_JAPANESE,
+            Language::Japanese => &statics::WORDLIST_JAPANESE,
             #[cfg(feature = "korean")]
-            Language::Korean => &lazy::WORDLIST_KOREAN,
+            Language::Korean => &statics::WORDLIST_KOREAN,
+            #[cfg(feature = "portuguese")]
+            Language::Portuguese => &statics::WORDLIST_PORTUGUESE,
             #[cfg(feature = "spanish")]
-            Language::Spanish => &lazy::WORDLIST_SPANISH,
+            Language::Spanish => &statics::WORDLIST_SPANISH,
+            Language::Custom(custom) => &custom.wordlist,
         }
     }
 
@@ -176,45 +416,370 @@ impl Language {
     ///
     /// The index of an individual word in the word list is used as the binary value of that word
     /// when the phrase is turned into a [`Seed`][Seed].
+    ///
+    /// With the `verify-wordlists` feature this is checked the same way as
+    /// [`Language::wordlist()`][wordlist].
+    ///
+    /// # Panics
+    ///
+    /// With the `verify-wordlists` feature, if the list was tampered with, the same way as
+    /// [`Language::wordlist()`][wordlist].
+    ///
+    /// [wordlist]: ./enum.Language.html#method.wordlist
     pub fn wordmap(&self) -> &'static WordMap {
+        self.try_wordmap()
+            .unwrap_or_else(|err| panic!("{:?} {}", self, err))
+    }
+
+    /// Same as `wordmap`, returning the error when the list fails verification
+    pub(crate) fn try_wordmap(&self) -> Result<&'static WordMap, Error> {
+        #[cfg(feature = "verify-wordlists")]
+        self.verify_on_first_use()?;
+
+        Ok(self.wordmap_unchecked())
+    }
+
+    fn wordmap_unchecked(&self) -> &'static WordMap {
         match *self {
-            Language::English => &lazy::WORDMAP_ENGLISH,
+            Language::English => &statics::WORDMAP_ENGLISH,
+            #[cfg(feature = "chinese-simplified")]
+            Language::ChineseSimplified => &statics::WORDMAP_CHINESE_SIMPLIFIED,
+            #[cfg(feature = "chinese-traditional")]
+            Language::ChineseTraditional => &statics::WORDMAP_CHINESE_TRADITIONAL,
+            #[cfg(feature = "czech")]
+            Language::Czech => &statics::WORDMAP_CZECH,
+            #[cfg(feature = "french")]
+            Language::French => &statics::WORDMAP_FRENCH,
+            #[cfg(feature = "italian")]
+            Language::Italian => &statics::WORDMAP_ITALIAN,
+            #[cfg(feature = "japanese")]
+            Language::Japanese => &statics::WORDMAP_JAPANESE,
+            #[cfg(feature = "korean")]
+            Language::Korean => &statics::WORDMAP_KOREAN,
+            #[cfg(feature = "portuguese")]
+            Language::Portuguese => &statics::WORDMAP_PORTUGUESE,
+            #[cfg(feature = "spanish")]
+            Language::Spanish => &statics::WORDMAP_SPANISH,
+            Language::Custom(custom) => &custom.wordmap,
+        }
+    }
+
+    /// Check the word list against the SHA-256 of the official BIP39 word list file
+    ///
+    /// The words are hashed one per line, the same as the files in the BIP repository, so a
+    /// changed, missing or reordered word is detected. The word map is checked to agree with the
+    /// list as well. Returns an `Error` of kind `ErrorKind::InvalidWordList` on a mismatch.
+    ///
+    /// Custom languages have no official hash, they are checked when the
+    /// [`CustomWordList`][CustomWordList] is created, so this always succeeds for them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bip39::Language;
+    ///
+    /// assert!(Language::English.verify_wordlist().is_ok());
+    /// ```
+    ///
+    /// [CustomWordList]: ./wordlist/struct.CustomWordList.html
+    pub fn verify_wordlist(&self) -> Result<(), Error> {
+        match self.official_sha256() {
+            Some(expected) => {
+                verify_wordlist(self.wordlist_unchecked(), self.wordmap_unchecked(), &expected)
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// SHA-256 of the official word list file for the built in languages
+    fn official_sha256(&self) -> Option<[u8; 32]> {
+        let hex = match *self {
+            Language::English => "2f5eed53a4727b4bf8880d8f3f199efc90e58503646d9ff8eff3a2ed3b24dbda",
             #[cfg(feature = "chinese-simplified")]
-            Language::ChineseSimplified => &lazy::WORDMAP_CHINESE_SIMPLIFIED,
+            Language::ChineseSimplified => {
+                "5c5942792bd8340cb8b27cd592f1015edf56a8c5b26276ee18a482428e7c5726"
+            }
             #[cfg(feature = "chinese-traditional")]
-            Language::ChineseTraditional => &lazy::WORDMAP_CHINESE_TRADITIONAL,
+            Language::ChineseTraditional => {
+                "417b26b3d8500a4ae3d59717d7011952db6fc2fb84b807f3f94ac734e89c1b5f"
+            }
+            #[cfg(feature = "czech")]
+            Language::Czech => "7e80e161c3e93d9554c2efb78d4e3cebf8fc727e9c52e03b83b94406bdcc95fc",
             #[cfg(feature = "french")]
-            Language::French => &lazy::WORDMAP_FRENCH,
+            Language::French => "ebc3959ab7801a1df6bac4fa7d970652f1df76b683cd2f4003c941c63d517e59",
             #[cfg(feature = "italian")]
-            Language::Italian => &lazy::WORDMAP_ITALIAN,
+            Language::Italian => "d392c49fdb700a24cd1fceb237c1f65dcc128f6b34a8aacb58b59384b5c648c2",
             #[cfg(feature = "japanese")]
-            Language::Japanese => &lazy::WORDMAP_JAPANESE,
+            Language::Japanese => {
+                "2eed0aef492291e061633d7ad8117f1a2b03eb80a29d0e4e3117ac2528d05ffd"
+            }
             #[cfg(feature = "korean")]
-            Language::Korean => &lazy::WORDMAP_KOREAN,
+            Language::Korean => "9e95f86c167de88f450f0aaf89e87f6624a57f973c67b516e338e8e8b8897f60",
+            #[cfg(feature = "portuguese")]
+            Language::Portuguese => {
+                "2685e9c194c82ae67e10ba59d9ea5345a23dc093e92276fc5361f6667d79cd3f"
+            }
             #[cfg(feature = "spanish")]
-            Language::Spanish => &lazy::WORDMAP_SPANISH,
+            Language::Spanish => "46846a5a0139d1e3cb77293e521c2865f7bcdb82c44e8d0a06a2cd0ecba48c0b",
+            Language::Custom(_) => return None,
+        };
+
+        let mut hash = [0u8; 32];
+
+        for (byte, pair) in hash.iter_mut().zip(hex.as_bytes().chunks(2)) {
+            let digits = std::str::from_utf8(pair).expect("hash is ASCII");
+
+            *byte = u8::from_str_radix(digits, 16).expect("hash is hex");
         }
+
+        Some(hash)
+    }
+
+    /// Verify each built in word list once, before it's first used
+    #[cfg(feature = "verify-wordlists")]
+    fn verify_on_first_use(&self) -> Result<(), Error> {
+        let verified = &self.wordlist_unchecked().verified;
+
+        if verified.load(Ordering::Acquire) {
+            return Ok(());
+        }
+
+        self.verify_wordlist()?;
+        verified.store(true, Ordering::Release);
+
+        Ok(())
+    }
+
+    /// Make a validated [`CustomWordList`][CustomWordList] usable as a `Language`
+    ///
+    /// The word list is leaked to get the `'static` lifetime every `Language` has, and kept for
+    /// the rest of the program under its code. Calling this again with the same code and the
+    /// same words returns the same `Language` without leaking anything more, while the same
+    /// code with different words returns an `Error` of kind `ErrorKind::InvalidWordList`. Memory
+    /// use grows with the number of distinct codes, so don't derive codes from untrusted input.
+    ///
+    /// [`Language::from_language_code()`][from_language_code] only knows the built in languages,
+    /// so formats that store the language code, like the keystore and UR encodings, refuse to
+    /// encode a mnemonic in a custom language.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bip39::{Language, Mnemonic};
+    /// use bip39::wordlist::CustomWordList;
+    ///
+    /// // A stand-in for a list loaded with `CustomWordList::from_file()`
+    /// let words: Vec<String> = (0..2048).map(|idx| format!("w{:04}", idx)).collect();
+    ///
+    /// let list = CustomWordList::new("x-test", &words.join("\n")).unwrap();
+    /// let lang = Language::custom(list).unwrap();
+    ///
+    /// let mnemonic = Mnemonic::from_entropy(&[0; 16], lang).unwrap();
+    ///
+    /// assert_eq!(mnemonic.phrase(), "w0000 w0000 w0000 w0000 w0000 w0000 w0000 w0000 w0000 w0000 w0000 w0003");
+    /// ```
+    ///
+    /// [CustomWordList]: ./wordlist/struct.CustomWordList.html
+    /// [from_language_code]: ./enum.Language.html#method.from_language_code
+    pub fn custom(list: CustomWordList) -> Result<Language, Error> {
+        let mut registered = CUSTOM_LANGUAGES
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        if let Some(&custom) = registered.iter().find(|custom| custom.code == list.code) {
+            let words = list.words.iter().map(String::as_str);
+
+            if !custom.wordlist.iter().eq(words) {
+                Err(ErrorKind::InvalidWordList(
+                    "language code already used by another word list",
+                ))?;
+            }
+
+            return Ok(Language::Custom(custom));
+        }
+
+        let wordlist = WordList::from_words(
+            list.words
+                .into_iter()
+                .map(|word| &*Box::leak(word.into_boxed_str()))
+                .collect(),
+        );
+        let wordmap = WordMap::from_wordlist(&wordlist);
+        let code: &'static str = Box::leak(list.code.into_boxed_str());
+
+        let custom: &'static CustomLanguage = Box::leak(Box::new(CustomLanguage {
+            code,
+            wordlist,
+            wordmap,
+        }));
+
+        registered.push(custom);
+
+        Ok(Language::Custom(custom))
+    }
+}
+
+/// A word list loaded at runtime and validated, but not yet usable as a [`Language`][Language]
+///
+/// Pass it to [`Language::custom()`][custom] to use it with [`Mnemonic`][Mnemonic] and
+/// [`Seed`][Seed].
+///
+/// [Language]: ../enum.Language.html
+/// [custom]: ../enum.Language.html#method.custom
+/// [Mnemonic]: ../struct.Mnemonic.html
+/// [Seed]: ../struct.Seed.html
+#[derive(Debug, Clone)]
+pub struct CustomWordList {
+    code: String,
+    words: Vec<String>,
+}
+
+impl CustomWordList {
+    /// Validate a word list, one word per line or otherwise separated by whitespace
+    ///
+    /// The list must have exactly 2048 distinct words, each in NFKD form and at most 64 bytes
+    /// long, and the code must be 1 to 32 bytes long. Returns an `Error` of kind
+    /// `ErrorKind::InvalidWordList` otherwise.
+    pub fn new(code: &str, words: &str) -> Result<CustomWordList, Error> {
+        if code.is_empty() || code.len() > MAX_CODE_BYTES {
+            Err(ErrorKind::InvalidWordList(
+                "language code must be 1 to 32 bytes long",
+            ))?;
+        }
+
+        let words: Vec<&str> = words.split_whitespace().collect();
+
+        if words.len() != 2048 {
+            Err(ErrorKind::InvalidWordList("word list must have 2048 words"))?;
+        }
+
+        if words.iter().any(|word| word.len() > MAX_WORD_BYTES) {
+            Err(ErrorKind::InvalidWordList("word longer than 64 bytes"))?;
+        }
+
+        if !words.iter().all(|word| unicode_normalization::is_nfkd(word)) {
+            Err(ErrorKind::InvalidWordList("word not in NFKD form"))?;
+        }
+
+        let mut sorted = words.clone();
+
+        sorted.sort_unstable();
+
+        if sorted.windows(2).any(|pair| pair[0] == pair[1]) {
+            Err(ErrorKind::InvalidWordList("duplicate word"))?;
+        }
+
+        Ok(CustomWordList {
+            code: code.to_string(),
+            words: words.into_iter().map(String::from).collect(),
+        })
+    }
+
+    /// Read and validate a word list file, see [`CustomWordList::new()`][new]
+    ///
+    /// [new]: ./struct.CustomWordList.html#method.new
+    pub fn from_file<P: AsRef<Path>>(code: &str, path: P) -> Result<CustomWordList, Error> {
+        CustomWordList::new(code, &std::fs::read_to_string(path)?)
+    }
+
+    /// Additionally require that the first `len` characters identify every word
+    ///
+    /// Lists meant for typing in or stamping on metal usually have this property for a `len`
+    /// of 4, see [`WordList::unique_prefix_len()`][unique_prefix_len].
+    ///
+    /// [unique_prefix_len]: ./struct.WordList.html#method.unique_prefix_len
+    pub fn require_unique_prefix(self, len: usize) -> Result<CustomWordList, Error> {
+        let mut sorted: Vec<&str> = self.words.iter().map(String::as_str).collect();
+
+        sorted.sort_unstable();
+
+        if unique_prefix_len(&sorted) > len {
+            Err(ErrorKind::InvalidWordList("words not unique in their prefix"))?;
+        }
+
+        Ok(self)
+    }
+}
+
+/// A custom word list in use as a [`Language`][Language]
+///
+/// [Language]: ../enum.Language.html
+pub struct CustomLanguage {
+    code: &'static str,
+    wordlist: WordList,
+    wordmap: WordMap,
+}
+
+impl PartialEq for CustomLanguage {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
     }
 }
 
-impl Default for Language {
-    fn default() -> Language {
-        Language::English
+impl fmt::Debug for CustomLanguage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("CustomLanguage").field(&self.code).finish()
+    }
+}
+
+/// Check a word list against the SHA-256 of its file, and the word map against the list
+fn verify_wordlist(
+    wordlist: &WordList,
+    wordmap: &WordMap,
+    expected: &[u8; 32],
+) -> Result<(), Error> {
+    let mut hasher = Sha256::new();
+
+    for word in wordlist.iter() {
+        hasher.update(word.as_bytes());
+        hasher.update(b"\n");
+    }
+
+    if wordlist.len() != 2048 || hasher.finalize()[..] != expected[..] {
+        Err(ErrorKind::InvalidWordList("does not match the official word list"))?;
+    }
+
+    for (idx, word) in wordlist.iter().enumerate() {
+        if wordmap.get_index(word) != Some(idx) {
+            Err(ErrorKind::InvalidWordList("word map does not match the word list"))?;
+        }
     }
+
+    Ok(())
+}
+
+/// Smallest prefix length that identifies every word in a sorted list
+fn unique_prefix_len(sorted: &[&str]) -> usize {
+    sorted
+        .windows(2)
+        .map(|pair| {
+            // The later of two sorted words needs one character past the common prefix
+            pair[0]
+                .chars()
+                .zip(pair[1].chars())
+                .take_while(|(a, b)| a == b)
+                .count()
+                + 1
+        })
+        .chain(sorted.iter().map(|word| word.chars().count().min(1)))
+        .max()
+        .unwrap_or(0)
 }
 
 #[cfg(test)]
 mod test {
-    use super::lazy;
+    use super::statics;
+    use super::CustomWordList;
     use super::Language;
-    use super::WordList;
+    use super::{verify_wordlist, WordList, WordMap};
+    use crate::{Mnemonic, Seed};
     #[cfg(target_arch = "wasm32")]
     use wasm_bindgen_test::*;
 
     #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
     #[cfg_attr(not(target_arch = "wasm32"), test)]
     fn words_by_prefix() {
-        let wl = &lazy::WORDLIST_ENGLISH;
+        let wl = &statics::WORDLIST_ENGLISH;
         let res = wl.get_words_by_prefix("woo");
         assert_eq!(res, ["wood","wool"]);
     }
@@ -222,7 +787,7 @@ mod test {
     #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
     #[cfg_attr(not(target_arch = "wasm32"), test)]
     fn all_words_by_prefix() {
-        let wl = &lazy::WORDLIST_ENGLISH;
+        let wl = &statics::WORDLIST_ENGLISH;
         let res = wl.get_words_by_prefix("");
         assert_eq!(res.len(), 2048);
     }
@@ -230,11 +795,283 @@ mod test {
     #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
     #[cfg_attr(not(target_arch = "wasm32"), test)]
     fn words_by_invalid_prefix() {
-        let wl = &lazy::WORDLIST_ENGLISH;
+        let wl = &statics::WORDLIST_ENGLISH;
         let res = wl.get_words_by_prefix("woof");
         assert!(res.is_empty());
     }
 
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg(feature = "default-langs")]
+    fn wordlists_match_source() {
+        let sources = [
+            (Language::English, include_str!("langs/english.txt")),
+            (Language::ChineseSimplified, include_str!("langs/chinese_simplified.txt")),
+            (Language::ChineseTraditional, include_str!("langs/chinese_traditional.txt")),
+            (Language::Czech, include_str!("langs/czech.txt")),
+            (Language::French, include_str!("langs/french.txt")),
+            (Language::Italian, include_str!("langs/italian.txt")),
+            (Language::Japanese, include_str!("langs/japanese.txt")),
+            (Language::Korean, include_str!("langs/korean.txt")),
+            (Language::Portuguese, include_str!("langs/portuguese.txt")),
+            (Language::Spanish, include_str!("langs/spanish.txt")),
+        ];
+
+        for &(lang, source) in &sources {
+            let words: Vec<&str> = source.split_whitespace().collect();
+
+            assert!(lang.wordlist().iter().eq(words.iter().copied()), "{:?}", lang);
+
+            for (idx, word) in words.iter().enumerate() {
+                assert_eq!(lang.wordmap().get_index(word), Some(idx), "{:?}", lang);
+            }
+        }
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg(feature = "default-langs")]
+    fn official_wordlists_verify() {
+        for lang in &[
+            Language::English,
+            Language::ChineseSimplified,
+            Language::ChineseTraditional,
+            Language::Czech,
+            Language::French,
+            Language::Italian,
+            Language::Japanese,
+            Language::Korean,
+            Language::Portuguese,
+            Language::Spanish,
+        ] {
+            assert!(lang.verify_wordlist().is_ok(), "{:?}", lang);
+        }
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn tampered_wordlist_fails_verification() {
+        let expected = Language::English.official_sha256().unwrap();
+        let words = english_words();
+
+        let mut swapped = words.clone();
+        swapped.swap(10, 11);
+        let wordlist = WordList::from_words(swapped);
+        let wordmap = WordMap::from_wordlist(&wordlist);
+        assert!(verify_wordlist(&wordlist, &wordmap, &expected).is_err());
+
+        let mut replaced = words.clone();
+        replaced[2047] = "zoom";
+        let wordlist = WordList::from_words(replaced);
+        let wordmap = WordMap::from_wordlist(&wordlist);
+        assert!(verify_wordlist(&wordlist, &wordmap, &expected).is_err());
+
+        let wordlist = WordList::from_words(words.clone());
+        let reversed = WordList::from_words(words.into_iter().rev().collect());
+        let wordmap = WordMap::from_wordlist(&reversed);
+        assert!(verify_wordlist(&wordlist, &wordmap, &expected).is_err());
+
+        let wordmap = WordMap::from_wordlist(&wordlist);
+        assert!(verify_wordlist(&wordlist, &wordmap, &expected).is_ok());
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn wordlist_lookups() {
+        let wl = &statics::WORDLIST_ENGLISH;
+        let wm = &statics::WORDMAP_ENGLISH;
+
+        assert_eq!(wl.len(), 2048);
+        assert!(!wl.is_empty());
+        assert_eq!(wl.iter().len(), 2048);
+        assert_eq!(wl.get(0), Some("abandon"));
+        assert_eq!(wl.get(2047), Some("zoo"));
+        assert_eq!(wl.get(2048), None);
+        assert_eq!(wl.index_of("zoo"), Some(2047));
+        assert_eq!(wl.index_of("woof"), None);
+        assert_eq!(wm.get_index("zoo"), Some(2047));
+        assert!(wl.contains("wood"));
+        assert!(!wl.contains("woo"));
+
+        for (idx, word) in wl.iter().enumerate() {
+            assert_eq!(wl.index_of(word), Some(idx));
+            assert_eq!(wm.get_index(word), Some(idx));
+        }
+    }
+
+    #[cfg(feature = "default-langs")]
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn words_by_prefix_matches_scan() {
+        let languages = [
+            Language::English,
+            Language::ChineseSimplified,
+            Language::ChineseTraditional,
+            Language::Czech,
+            Language::French,
+            Language::Italian,
+            Language::Japanese,
+            Language::Korean,
+            Language::Portuguese,
+            Language::Spanish,
+        ];
+
+        for &lang in &languages {
+            let wl = lang.wordlist();
+            let mut prefixes: Vec<String> = vec![String::new()];
+
+            // Every one and two character prefix, and all prefixes of every 16th word
+            for (idx, word) in wl.iter().enumerate() {
+                let chars: Vec<char> = word.chars().collect();
+                let longest = if idx % 16 == 0 { chars.len() } else { 2 };
+
+                for len in 1..=longest.min(chars.len()) {
+                    prefixes.push(chars[..len].iter().collect());
+                }
+            }
+
+            prefixes.sort_unstable();
+            prefixes.dedup();
+
+            for prefix in &prefixes {
+                let mut expected: Vec<&str> =
+                    wl.iter().filter(|word| word.starts_with(&prefix[..])).collect();
+                expected.sort_unstable();
+
+                assert_eq!(wl.get_words_by_prefix(prefix), &expected[..], "{:?}", lang);
+            }
+
+            assert_eq!(wl.get_words_by_prefix("woof").len(), 0);
+        }
+    }
+
+    #[cfg(feature = "default-langs")]
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn unique_prefix_len() {
+        let expected = [
+            (Language::English, 4),
+            (Language::ChineseSimplified, 1),
+            (Language::ChineseTraditional, 1),
+            (Language::Czech, 4),
+            (Language::French, 6),
+            (Language::Italian, 4),
+            (Language::Japanese, 5),
+            (Language::Korean, 5),
+            (Language::Portuguese, 4),
+            (Language::Spanish, 5),
+        ];
+
+        for &(lang, len) in &expected {
+            let wl = lang.wordlist();
+            let prefixes: std::collections::HashSet<String> = wl
+                .iter()
+                .map(|word| word.chars().take(len).collect())
+                .collect();
+
+            assert_eq!(wl.unique_prefix_len(), len, "{:?}", lang);
+            assert_eq!(prefixes.len(), 2048, "{:?}", lang);
+        }
+    }
+
+    fn english_words() -> Vec<&'static str> {
+        statics::WORDLIST_ENGLISH.iter().collect()
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn custom_wordlist() {
+        let list = CustomWordList::new("x-en", &english_words().join("\n")).unwrap();
+        let lang = Language::custom(list.require_unique_prefix(4).unwrap()).unwrap();
+
+        assert_eq!(lang.language_code(), "x-en");
+        assert_ne!(lang, Language::English);
+        assert_eq!(lang, lang);
+        assert_eq!(lang.wordlist().unique_prefix_len(), 4);
+        assert_eq!(lang.wordmap().get_index("zoo"), Some(2047));
+        assert_eq!(lang.wordlist().get_words_by_prefix("woo"), ["wood", "wool"]);
+
+        let phrase = "park remain person kitchen mule spell knee armed position rail grid ankle";
+        let custom = Mnemonic::from_phrase(phrase, lang).unwrap();
+        let english = Mnemonic::from_phrase(phrase, Language::English).unwrap();
+
+        assert_eq!(custom.entropy(), english.entropy());
+        assert_eq!(Seed::new(&custom, "TREZOR"), Seed::new(&english, "TREZOR"));
+        assert!(Mnemonic::from_phrase_ct(phrase, lang).is_ok());
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn custom_language_is_interned() {
+        let mut words = english_words();
+        let list = CustomWordList::new("x-interned", &words.join(" ")).unwrap();
+        let lang = Language::custom(list.clone()).unwrap();
+
+        assert_eq!(Language::custom(list).unwrap(), lang);
+
+        words.swap(0, 1);
+        let other = CustomWordList::new("x-interned", &words.join(" ")).unwrap();
+        assert!(Language::custom(other).is_err());
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn custom_wordlist_from_file() {
+        let path = std::env::temp_dir().join(format!("bip39-custom-{}.txt", std::process::id()));
+
+        std::fs::write(&path, english_words().join("\n") + "\n").unwrap();
+
+        let list = CustomWordList::from_file("x-en", &path);
+
+        std::fs::remove_file(&path).unwrap();
+        assert!(list.is_ok());
+        assert!(CustomWordList::from_file("x-en", &path).is_err());
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn invalid_custom_wordlist() {
+        let mut words = english_words();
+        let long = "a".repeat(65);
+        let composed = "\u{e9}t\u{e9}";
+
+        assert!(CustomWordList::new("x", &words[..2047].join(" ")).is_err());
+        assert!(CustomWordList::new("", &words.join(" ")).is_err());
+        assert!(CustomWordList::new(&"x".repeat(33), &words.join(" ")).is_err());
+
+        words[1] = "abandon";
+        assert!(CustomWordList::new("x", &words.join(" ")).is_err());
+
+        words[1] = &long;
+        assert!(CustomWordList::new("x", &words.join(" ")).is_err());
+
+        words[1] = composed;
+        assert!(CustomWordList::new("x", &words.join(" ")).is_err());
+
+        words[1] = "abanda";
+        let list = CustomWordList::new("x", &words.join(" ")).unwrap();
+        assert!(list.clone().require_unique_prefix(4).is_err());
+        assert!(list.require_unique_prefix(6).is_ok());
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn get_bits_ct_matches_wordmap() {
+        let wl = &statics::WORDLIST_ENGLISH;
+        let wm = &statics::WORDMAP_ENGLISH;
+
+        for idx in (0..2048u16).step_by(31).chain(Some(2047)) {
+            let word = wl.get_word(idx.into());
+            let bits = wl.get_bits_ct(word).unwrap();
+
+            assert_eq!(u16::from(bits), u16::from(wm.get_bits(word).unwrap()));
+        }
+
+        assert!(bool::from(wl.get_bits_ct("woof").is_none()));
+        assert!(bool::from(wl.get_bits_ct("").is_none()));
+        assert!(bool::from(wl.get_bits_ct(&"a".repeat(100)).is_none()));
+    }
+
     fn is_wordlist_nfkd(wl: &WordList) -> bool {
         for idx in 0..2047 {
             let word = wl.get_word(idx.into());
@@ -242,56 +1079,70 @@ mod test {
                 return false;
             }
         }
-        return true;
+        true
     }
 
     #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
     #[cfg_attr(not(target_arch = "wasm32"), test)]
     #[cfg(feature = "chinese-simplified")]
     fn chinese_simplified_wordlist_is_nfkd() {
-        assert!(is_wordlist_nfkd(&lazy::WORDLIST_CHINESE_SIMPLIFIED));
+        assert!(is_wordlist_nfkd(&statics::WORDLIST_CHINESE_SIMPLIFIED));
     }
 
     #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
     #[cfg_attr(not(target_arch = "wasm32"), test)]
     #[cfg(feature = "chinese-traditional")]
     fn chinese_traditional_wordlist_is_nfkd() {
-        assert!(is_wordlist_nfkd(&lazy::WORDLIST_CHINESE_TRADITIONAL));
+        assert!(is_wordlist_nfkd(&statics::WORDLIST_CHINESE_TRADITIONAL));
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg(feature = "czech")]
+    fn czech_wordlist_is_nfkd() {
+        assert!(is_wordlist_nfkd(&statics::WORDLIST_CZECH));
     }
 
     #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
     #[cfg_attr(not(target_arch = "wasm32"), test)]
     #[cfg(feature = "french")]
     fn french_wordlist_is_nfkd() {
-        assert!(is_wordlist_nfkd(&lazy::WORDLIST_FRENCH));
+        assert!(is_wordlist_nfkd(&statics::WORDLIST_FRENCH));
     }
 
     #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
     #[cfg_attr(not(target_arch = "wasm32"), test)]
     #[cfg(feature = "italian")]
     fn italian_wordlist_is_nfkd() {
-        assert!(is_wordlist_nfkd(&lazy::WORDLIST_ITALIAN));
+        assert!(is_wordlist_nfkd(&statics::WORDLIST_ITALIAN));
     }
 
     #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
     #[cfg_attr(not(target_arch = "wasm32"), test)]
     #[cfg(feature = "japanese")]
     fn japanese_wordlist_is_nfkd() {
-        assert!(is_wordlist_nfkd(&lazy::WORDLIST_JAPANESE));
+        assert!(is_wordlist_nfkd(&statics::WORDLIST_JAPANESE));
     }
 
     #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
     #[cfg_attr(not(target_arch = "wasm32"), test)]
     #[cfg(feature = "korean")]
     fn korean_wordlist_is_nfkd() {
-        assert!(is_wordlist_nfkd(&lazy::WORDLIST_KOREAN));
+        assert!(is_wordlist_nfkd(&statics::WORDLIST_KOREAN));
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg(feature = "portuguese")]
+    fn portuguese_wordlist_is_nfkd() {
+        assert!(is_wordlist_nfkd(&statics::WORDLIST_PORTUGUESE));
     }
 
     #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
     #[cfg_attr(not(target_arch = "wasm32"), test)]
     #[cfg(feature = "spanish")]
     fn spanish_wordlist_is_nfkd() {
-        assert!(is_wordlist_nfkd(&lazy::WORDLIST_SPANISH));
+        assert!(is_wordlist_nfkd(&statics::WORDLIST_SPANISH));
     }
 
     #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
@@ -323,6 +1174,16 @@ mod test {
         );
     }
 
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg(feature = "czech")]
+    fn from_language_code_cs() {
+        assert_eq!(
+            Language::from_language_code("Cs").expect("cs is a valid language"),
+            Language::Czech
+        );
+    }
+
     #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
     #[cfg_attr(not(target_arch = "wasm32"), test)]
     #[cfg(feature = "french")]
@@ -363,6 +1224,16 @@ mod test {
         );
     }
 
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg(feature = "portuguese")]
+    fn from_language_code_pt() {
+        assert_eq!(
+            Language::from_language_code("PT").expect("pt is a valid language"),
+            Language::Portuguese
+        );
+    }
+
     #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
     #[cfg_attr(not(target_arch = "wasm32"), test)]
     #[cfg(feature = "spanish")]
@@ -378,4 +1249,27 @@ mod test {
     fn from_invalid_language_code() {
         assert_eq!(Language::from_language_code("not a real language"), None);
     }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg(feature = "default-langs")]
+    fn language_code_round_trip() {
+        for lang in &[
+            Language::English,
+            Language::ChineseSimplified,
+            Language::ChineseTraditional,
+            Language::Czech,
+            Language::French,
+            Language::Italian,
+            Language::Japanese,
+            Language::Korean,
+            Language::Portuguese,
+            Language::Spanish,
+        ] {
+            assert_eq!(
+                Language::from_language_code(lang.language_code()),
+                Some(*lang)
+            );
+        }
+    }
 }
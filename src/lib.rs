@@ -24,20 +24,44 @@
 //! let seed_bytes: &[u8] = seed.as_bytes();
 //!
 //! // print the HD wallet seed as a hex string
-//! println!("{:X}", seed);
+//! println!("{:X}", seed.expose());
 //! ```
 //!
 mod error;
+mod exposed;
+#[cfg(feature = "fingerprint")]
+mod fingerprint;
 mod language;
+mod metal;
 mod mnemonic;
 mod mnemonic_type;
+mod secret;
 mod seed;
+mod seedqr;
+mod self_test;
 mod util;
+mod weakness;
 
 mod crypto;
 
+pub mod algorand;
+#[cfg(feature = "keystore")]
+pub mod keystore;
+pub mod passphrase;
+#[cfg(feature = "ur")]
+pub mod ur;
+pub mod wordlist;
+
 pub use error::ErrorKind;
+pub use exposed::Exposed;
 pub use language::Language;
+pub use metal::PunchRow;
 pub use mnemonic::Mnemonic;
 pub use mnemonic_type::MnemonicType;
-pub use seed::Seed;
+#[cfg(feature = "mlock")]
+pub use secret::MemoryLock;
+pub use seed::{Normalization, SecretPassphrase, Seed, SeedOptions};
+#[cfg(feature = "qr")]
+pub use seedqr::QrMatrix;
+pub use self_test::self_test;
+pub use weakness::{Weakness, WeaknessReport};
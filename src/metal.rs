@@ -0,0 +1,379 @@
+//! Formats used to record a mnemonic on steel backup plates.
+//!
+//! Plates either record the number of each word in the word list, from 1 to 2048, punch its
+//! 11-bit value, or stamp its first four letters. All of these formats are built on the word
+//! indices from [`Mnemonic::word_indices()`][word_indices], and restoring from any of them
+//! validates the checksum like [`Mnemonic::from_phrase()`][Mnemonic::from_phrase()] does.
+//!
+//! Four letters identify every word in all of the word lists except Korean, where the words
+//! decompose into jamo. A letter is counted together with any combining marks that follow it.
+//!
+//! [word_indices]: ../mnemonic/struct.Mnemonic.html#method.word_indices
+//! [Mnemonic::from_phrase()]: ../mnemonic/struct.Mnemonic.html#method.from_phrase
+
+use crate::error::ErrorKind;
+use crate::language::{Language, WordList};
+use crate::mnemonic::Mnemonic;
+use crate::util::Bits11;
+use anyhow::Error;
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+use zeroize::Zeroizing;
+
+/// Number of letters stamped for each word
+const STAMP_LETTERS: usize = 4;
+
+/// One word as an 11-bit punch pattern, most significant bit (`1024`) first
+pub type PunchRow = [bool; 11];
+
+impl Mnemonic {
+    /// Get the number of each word as printed on backup plates, from `1` to `2048`
+    ///
+    /// This is the [word index][word_indices] plus one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bip39::{Mnemonic, Language};
+    ///
+    /// let phrase = "park remain person kitchen mule spell knee armed position rail grid ankle";
+    /// let mnemonic = Mnemonic::from_phrase(phrase, Language::English).unwrap();
+    ///
+    /// assert_eq!(mnemonic.plate_numbers()[..3], [1283, 1453, 1307]);
+    /// ```
+    ///
+    /// [word_indices]: ../mnemonic/struct.Mnemonic.html#method.word_indices
+    pub fn plate_numbers(&self) -> Zeroizing<Vec<u16>> {
+        let mut numbers = self.word_indices();
+
+        for number in numbers.iter_mut() {
+            *number += 1;
+        }
+
+        numbers
+    }
+
+    /// Create a [`Mnemonic`][Mnemonic] from the word numbers on a backup plate, from `1` to
+    /// `2048`
+    ///
+    /// A number out of range, including `0`, returns an `Error` of kind `ErrorKind::InvalidWord`
+    /// with its position.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bip39::{Mnemonic, Language};
+    ///
+    /// let numbers = [1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 4];
+    ///
+    /// let mnemonic = Mnemonic::from_plate_numbers(&numbers, Language::English).unwrap();
+    ///
+    /// assert_eq!(mnemonic.phrase(), "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about");
+    /// ```
+    ///
+    /// [Mnemonic]: ../mnemonic/struct.Mnemonic.html
+    pub fn from_plate_numbers(numbers: &[u16], lang: Language) -> Result<Mnemonic, Error> {
+        let entropy =
+            Mnemonic::bits_to_entropy(numbers.iter().enumerate().map(|(idx, &number)| {
+                if (1..=2048).contains(&number) {
+                    Ok(Bits11::from(number - 1))
+                } else {
+                    Err(ErrorKind::InvalidWord(idx))
+                }
+            }))?;
+
+        Mnemonic::from_entropy_unchecked(entropy, lang)
+    }
+
+    /// Get the punch pattern of each word, `true` marks a hole
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bip39::{Mnemonic, Language};
+    ///
+    /// let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+    /// let mnemonic = Mnemonic::from_phrase(phrase, Language::English).unwrap();
+    ///
+    /// let grid = mnemonic.to_punch_grid();
+    ///
+    /// assert_eq!(grid.len(), 12);
+    /// assert_eq!(grid[11], [false, false, false, false, false, false, false, false, false, true, true]);
+    /// ```
+    pub fn to_punch_grid(&self) -> Zeroizing<Vec<PunchRow>> {
+        let indices = self.word_indices();
+        let mut grid = Zeroizing::new(Vec::with_capacity(indices.len()));
+
+        for &index in indices.iter() {
+            let mut row = [false; 11];
+
+            for (bit, hole) in row.iter_mut().enumerate() {
+                *hole = index & (1024 >> bit) != 0;
+            }
+
+            grid.push(row);
+        }
+
+        grid
+    }
+
+    /// Create a [`Mnemonic`][Mnemonic] from punch patterns read off a plate
+    ///
+    /// [Mnemonic]: ../mnemonic/struct.Mnemonic.html
+    pub fn from_punch_grid(grid: &[PunchRow], lang: Language) -> Result<Mnemonic, Error> {
+        let entropy = Mnemonic::bits_to_entropy(grid.iter().map(|row| {
+            let index = row.iter().fold(0u16, |acc, &hole| acc << 1 | hole as u16);
+
+            Ok(Bits11::from(index))
+        }))?;
+
+        Mnemonic::from_entropy_unchecked(entropy, lang)
+    }
+
+    /// Get the first four letters of each word, separated by spaces
+    ///
+    /// Words of four letters or fewer are stamped in full. Returns an `Error` of kind
+    /// `ErrorKind::AmbiguousStamp` if a stamp would match more than one word in the list, which
+    /// can only happen with Korean.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bip39::{Mnemonic, Language};
+    ///
+    /// let phrase = "park remain person kitchen mule spell knee armed position rail grid ankle";
+    /// let mnemonic = Mnemonic::from_phrase(phrase, Language::English).unwrap();
+    ///
+    /// assert_eq!(
+    ///     &*mnemonic.to_stamps().unwrap(),
+    ///     "park rema pers kitc mule spel knee arme posi rail grid ankl"
+    /// );
+    /// ```
+    pub fn to_stamps(&self) -> Result<Zeroizing<String>, Error> {
+        let wordlist = self.language().try_wordlist()?;
+        let mut stamps = Zeroizing::new(String::with_capacity(self.phrase().len()));
+
+        for (idx, word) in self.phrase().split(' ').enumerate() {
+            let word_stamp = stamp(word);
+
+            if find_stamp(wordlist, word_stamp, idx)? != Some(word) {
+                Err(ErrorKind::AmbiguousStamp(idx))?;
+            }
+
+            if idx > 0 {
+                stamps.push(' ');
+            }
+
+            stamps.push_str(word_stamp);
+        }
+
+        Ok(stamps)
+    }
+
+    /// Create a [`Mnemonic`][Mnemonic] from the four letter stamps of each word
+    ///
+    /// Stamps are separated by whitespace and matched case insensitively, so they can be typed in
+    /// as they appear on the plate.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bip39::{Mnemonic, Language};
+    ///
+    /// let stamps = "PARK REMA PERS KITC MULE SPEL KNEE ARME POSI RAIL GRID ANKL";
+    /// let mnemonic = Mnemonic::from_stamps(stamps, Language::English).unwrap();
+    ///
+    /// assert_eq!(
+    ///     mnemonic.phrase(),
+    ///     "park remain person kitchen mule spell knee armed position rail grid ankle"
+    /// );
+    /// ```
+    ///
+    /// [Mnemonic]: ../mnemonic/struct.Mnemonic.html
+    pub fn from_stamps(stamps: &str, lang: Language) -> Result<Mnemonic, Error> {
+        let wordlist = lang.try_wordlist()?;
+        let wordmap = lang.try_wordmap()?;
+
+        let entropy = Mnemonic::bits_to_entropy(stamps.split_whitespace().enumerate().map(
+            |(idx, word_stamp)| {
+                let word_stamp: Zeroizing<String> =
+                    Zeroizing::new(word_stamp.nfkd().flat_map(char::to_lowercase).collect());
+
+                find_stamp(wordlist, &word_stamp, idx)?
+                    .and_then(|word| wordmap.get_bits(word))
+                    .ok_or(ErrorKind::InvalidWord(idx))
+            },
+        ))?;
+
+        Mnemonic::from_entropy_unchecked(entropy, lang)
+    }
+}
+
+/// The first four letters of `word`, keeping combining marks with their letter
+fn stamp(word: &str) -> &str {
+    let mut letters = 0;
+
+    for (offset, ch) in word.char_indices() {
+        if !is_combining_mark(ch) {
+            if letters == STAMP_LETTERS {
+                return &word[..offset];
+            }
+
+            letters += 1;
+        }
+    }
+
+    word
+}
+
+/// Find the only word with the given stamp
+fn find_stamp(
+    wordlist: &WordList,
+    word_stamp: &str,
+    idx: usize,
+) -> Result<Option<&'static str>, ErrorKind> {
+    let mut found = None;
+
+    for bits in 0..2048u16 {
+        let word = wordlist.get_word(Bits11::from(bits));
+
+        if stamp(word) == word_stamp {
+            if found.is_some() {
+                return Err(ErrorKind::AmbiguousStamp(idx));
+            }
+
+            found = Some(word);
+        }
+    }
+
+    Ok(found)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::mnemonic_type::MnemonicType;
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    const PHRASE: &str =
+        "park remain person kitchen mule spell knee armed position rail grid ankle";
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn plate_numbers_round_trip() {
+        let mnemonic = Mnemonic::from_phrase(PHRASE, Language::English).unwrap();
+        let numbers = mnemonic.plate_numbers();
+
+        // "park" is word 1282 counting from 0
+        assert_eq!(numbers[0], 1283);
+        assert_eq!(
+            Mnemonic::from_plate_numbers(&numbers, Language::English).unwrap(),
+            mnemonic
+        );
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn plate_numbers_invalid() {
+        let mut numbers = [1u16; 12];
+
+        numbers[11] = 4;
+        assert!(Mnemonic::from_plate_numbers(&numbers, Language::English).is_ok());
+
+        for &number in &[0, 2049] {
+            numbers[5] = number;
+
+            match Mnemonic::from_plate_numbers(&numbers, Language::English) {
+                Err(err) => match err.downcast_ref::<ErrorKind>() {
+                    Some(ErrorKind::InvalidWord(5)) => (),
+                    _ => panic!("expected InvalidWord(5), got {:?}", err),
+                },
+                Ok(_) => panic!("number {} was accepted", number),
+            }
+        }
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn punch_grid_round_trip() {
+        let mnemonic = Mnemonic::from_phrase(PHRASE, Language::English).unwrap();
+        let grid = mnemonic.to_punch_grid();
+
+        // "park" is word 1282, 0b10100000010
+        assert_eq!(
+            grid[0],
+            [true, false, true, false, false, false, false, false, false, true, false]
+        );
+        assert_eq!(
+            Mnemonic::from_punch_grid(&grid, Language::English).unwrap(),
+            mnemonic
+        );
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn punch_grid_invalid() {
+        let mnemonic = Mnemonic::from_phrase(PHRASE, Language::English).unwrap();
+        let mut grid = mnemonic.to_punch_grid();
+
+        grid[11][10] = !grid[11][10];
+        assert!(Mnemonic::from_punch_grid(&grid, Language::English).is_err());
+        assert!(Mnemonic::from_punch_grid(&grid[..11], Language::English).is_err());
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn stamps_round_trip() {
+        for _ in 0..10 {
+            let mnemonic = Mnemonic::new(MnemonicType::Words24, Language::English);
+            let stamps = mnemonic.to_stamps().unwrap();
+
+            assert_eq!(
+                Mnemonic::from_stamps(&stamps, Language::English).unwrap(),
+                mnemonic
+            );
+        }
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn stamps_short_words() {
+        let phrase = "legal winner thank year wave sausage worth useful legal winner thank yellow";
+        let mnemonic = Mnemonic::from_phrase(phrase, Language::English).unwrap();
+        let stamps = mnemonic.to_stamps().unwrap();
+
+        assert_eq!(
+            &*stamps,
+            "lega winn than year wave saus wort usef lega winn than yell"
+        );
+        assert_eq!(
+            Mnemonic::from_stamps(&stamps, Language::English).unwrap(),
+            mnemonic
+        );
+        assert_eq!(stamp("act"), "act");
+    }
+
+    #[cfg(feature = "default-langs")]
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn stamps_accented() {
+        let mnemonic = Mnemonic::new(MnemonicType::Words24, Language::French);
+        let stamps = mnemonic.to_stamps().unwrap();
+        let composed: String = stamps.nfc().collect();
+
+        assert_eq!(
+            Mnemonic::from_stamps(&composed, Language::French).unwrap(),
+            mnemonic
+        );
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn stamps_invalid() {
+        let stamps = "park rema pers kitc mule spel knee arme posi rail grid zzzz";
+
+        assert!(Mnemonic::from_stamps(stamps, Language::English).is_err());
+        assert!(Mnemonic::from_stamps("park rema", Language::English).is_err());
+    }
+}
@@ -2,12 +2,16 @@
 use crate::crypto::gen_random_bytes;
 use crate::crypto::sha256_first_byte;
 use crate::error::ErrorKind;
+use crate::exposed::Exposed;
 use crate::language::Language;
 use crate::mnemonic_type::MnemonicType;
-use crate::util::{checksum, BitWriter, IterExt};
+#[cfg(feature = "mlock")]
+use crate::secret::MemoryLock;
+use crate::secret::{SecretBytes, SecretString};
+use crate::util::{checksum, BitWriter, Bits11, IterExt};
 use anyhow::Error;
 use std::fmt;
-use std::mem;
+use subtle::{Choice, ConstantTimeEq};
 use unicode_normalization::UnicodeNormalization;
 use zeroize::Zeroizing;
 
@@ -20,7 +24,7 @@ use zeroize::Zeroizing;
 ///
 /// You can get the HD wallet [`Seed`][Seed] from a [`Mnemonic`][Mnemonic] by calling [`Seed::new()`][Seed::new()].
 /// From there you can either get the raw byte value with [`Seed::as_bytes()`][Seed::as_bytes()], or the hex
-/// representation using Rust formatting: `format!("{:X}", seed)`.
+/// representation using Rust formatting: `format!("{:X}", seed.expose())`.
 ///
 /// You can also get the original entropy value back from a [`Mnemonic`][Mnemonic] with [`Mnemonic::entropy()`][Mnemonic::entropy()],
 /// but beware that the entropy value is **not the same thing** as an HD wallet seed, and should
@@ -28,19 +32,23 @@ use zeroize::Zeroizing;
 ///
 /// [`Mnemonic`][Mnemonic] implements [`Zeroize`][Zeroize], so it's bytes will be zeroed when it's dropped.
 ///
+/// The `Debug` output only shows the language and word count. To format the phrase or the entropy
+/// use [`Mnemonic::expose()`][Mnemonic::expose()].
+///
 /// [Mnemonic]: ./mnemonic/struct.Mnemonic.html
 /// [Mnemonic::new()]: ./mnemonic/struct.Mnemonic.html#method.new
 /// [Mnemonic::from_phrase()]: ./mnemonic/struct.Mnemonic.html#method.from_phrase
 /// [Mnemonic::entropy()]: ./mnemonic/struct.Mnemonic.html#method.entropy
+/// [Mnemonic::expose()]: ./mnemonic/struct.Mnemonic.html#method.expose
 /// [Seed]: ./seed/struct.Seed.html
 /// [Seed::new()]: ./seed/struct.Seed.html#method.new
 /// [Seed::as_bytes()]: ./seed/struct.Seed.html#method.as_bytes
 ///
 #[derive(Clone)]
 pub struct Mnemonic {
-    phrase: Zeroizing<String>,
+    phrase: SecretString,
     lang: Language,
-    entropy: Zeroizing<Vec<u8>>,
+    entropy: SecretBytes,
 }
 
 impl Mnemonic {
@@ -61,11 +69,56 @@ impl Mnemonic {
     /// assert_eq!(phrase.split(" ").count(), 12);
     /// ```
     ///
+    /// # Panics
+    ///
+    /// If the random number generator fails its health tests twice in a row, see
+    /// [`Mnemonic::try_new()`][Mnemonic::try_new()], with the `self-test` feature if the
+    /// [self test][self_test()] fails, and with the `verify-wordlists` feature if the word list
+    /// was tampered with.
+    ///
     /// [Mnemonic]: ./mnemonic/struct.Mnemonic.html
     /// [Mnemonic::phrase()]: ./mnemonic/struct.Mnemonic.html#method.phrase
+    /// [Mnemonic::try_new()]: ./mnemonic/struct.Mnemonic.html#method.try_new
+    /// [self_test()]: ./fn.self_test.html
     #[cfg(feature = "rand")]
     pub fn new(mtype: MnemonicType, lang: Language) -> Mnemonic {
-        let entropy = gen_random_bytes(mtype.entropy_bits() / 8);
+        // A working generator fails the health tests about once in 2^30 calls, retrying once
+        // makes that negligible while a broken one still fails
+        match Mnemonic::try_new(mtype, lang).or_else(|_| Mnemonic::try_new(mtype, lang)) {
+            Ok(mnemonic) => mnemonic,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Generates a new [`Mnemonic`][Mnemonic], or returns an `Error` of kind
+    /// `ErrorKind::RngHealthTest` if the random number generator looks broken
+    ///
+    /// The random bytes are checked with continuous health tests modelled on NIST SP 800-90B: a
+    /// repetition count test, an adaptive proportion test, and a check that the output isn't
+    /// stuck repeating itself. A working generator fails them about once in 2^30 calls, so an
+    /// error almost certainly means the generator is broken. Retrying once is reasonable, but a
+    /// second failure in a row means the generator can't be trusted.
+    ///
+    /// With the `self-test` feature, a failed [self test][self_test()] is returned as an `Error`
+    /// of kind `ErrorKind::SelfTestFailed`, and with the `verify-wordlists` feature a tampered
+    /// word list as an `Error` of kind `ErrorKind::InvalidWordList`, as they are by every other
+    /// constructor that returns a `Result`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bip39::{Mnemonic, MnemonicType, Language};
+    ///
+    /// let mnemonic = Mnemonic::try_new(MnemonicType::Words24, Language::English).unwrap();
+    ///
+    /// assert_eq!(mnemonic.phrase().split(" ").count(), 24);
+    /// ```
+    ///
+    /// [Mnemonic]: ./mnemonic/struct.Mnemonic.html
+    /// [self_test()]: ./fn.self_test.html
+    #[cfg(feature = "rand")]
+    pub fn try_new(mtype: MnemonicType, lang: Language) -> Result<Mnemonic, Error> {
+        let entropy = gen_random_bytes(mtype.entropy_bits() / 8)?;
 
         Mnemonic::from_entropy_unchecked(entropy, lang)
     }
@@ -81,7 +134,7 @@ impl Mnemonic {
     /// let mnemonic = Mnemonic::from_entropy(entropy, Language::English).unwrap();
     ///
     /// assert_eq!("crop cash unable insane eight faith inflict route frame loud box vibrant", mnemonic.phrase());
-    /// assert_eq!("33E46BB13A746EA41CDDE45C90846A79", format!("{:X}", mnemonic));
+    /// assert_eq!("33E46BB13A746EA41CDDE45C90846A79", format!("{:X}", mnemonic.expose()));
     /// ```
     ///
     /// [Mnemonic]: ../mnemonic/struct.Mnemonic.html
@@ -89,17 +142,33 @@ impl Mnemonic {
         // Validate entropy size
         MnemonicType::for_key_size(entropy.len() * 8)?;
 
-        Ok(Self::from_entropy_unchecked(entropy, lang))
+        Self::from_entropy_unchecked(Zeroizing::new(entropy.to_vec()), lang)
     }
 
-    fn from_entropy_unchecked<E>(entropy: E, lang: Language) -> Mnemonic
-    where
-        E: Into<Vec<u8>>,
-    {
-        let entropy = Zeroizing::new(entropy.into());
-        let wordlist = lang.wordlist();
+    pub(crate) fn from_entropy_unchecked(
+        entropy: Zeroizing<Vec<u8>>,
+        lang: Language,
+    ) -> Result<Mnemonic, Error> {
+        #[cfg(feature = "self-test")]
+        crate::self_test::run_on_first_use()?;
 
-        let checksum_byte = sha256_first_byte(&entropy);
+        let phrase = Mnemonic::entropy_to_phrase(&entropy, lang)?;
+
+        Ok(Mnemonic {
+            phrase: SecretString::from_string(phrase),
+            lang,
+            entropy: SecretBytes::from_vec(entropy),
+        })
+    }
+
+    /// Encode entropy of a valid size as a phrase, including the checksum
+    pub(crate) fn entropy_to_phrase(
+        entropy: &[u8],
+        lang: Language,
+    ) -> Result<Zeroizing<String>, Error> {
+        let wordlist = lang.try_wordlist()?;
+
+        let checksum_byte = sha256_first_byte(entropy);
 
         // First, create a byte iterator for the given entropy and the first byte of the
         // hash of the entropy that will serve as the checksum (up to 8 bits for biggest
@@ -110,20 +179,12 @@ impl Mnemonic {
         //
         // Given the entropy is of correct size, this ought to give us the correct word
         // count.
-        let phrase = Zeroizing::new(
-            entropy
-                .iter()
-                .chain(Some(&checksum_byte))
-                .bits()
-                .map(|bits| wordlist.get_word(bits))
-                .join(" "),
-        );
-
-        Mnemonic {
-            phrase,
-            lang,
-            entropy,
-        }
+        Ok(entropy
+            .iter()
+            .chain(Some(&checksum_byte))
+            .bits()
+            .map(|bits| wordlist.get_word(bits))
+            .join(" "))
     }
 
     /// Create a [`Mnemonic`][Mnemonic] from an existing mnemonic phrase
@@ -144,22 +205,55 @@ impl Mnemonic {
     ///
     /// [Mnemonic]: ../mnemonic/struct.Mnemonic.html
     pub fn from_phrase(phrase: &str, lang: Language) -> Result<Mnemonic, Error> {
-        let phrase = Zeroizing::new(
-            phrase
-                .split_whitespace()
-                .map(|w| w.nfkd())
-                .join::<String>(" "),
-        );
+        Mnemonic::from_phrase_with(phrase, lang, Mnemonic::phrase_to_entropy)
+    }
+
+    /// Create a [`Mnemonic`][Mnemonic] from an existing mnemonic phrase, hardened against timing
+    /// side channels
+    ///
+    /// Works like [`Mnemonic::from_phrase()`][Mnemonic::from_phrase()], but every word is looked
+    /// up by comparing it against the entire word list in constant time, instead of using a hash
+    /// map. This is considerably slower, and only worth it where an attacker can measure how long
+    /// validating a secret phrase takes.
+    ///
+    /// Note that the number and length of the words, and the position of an invalid word, are
+    /// not hidden.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bip39::{Mnemonic, Language};
+    ///
+    /// let phrase = "park remain person kitchen mule spell knee armed position rail grid ankle";
+    /// let mnemonic = Mnemonic::from_phrase_ct(phrase, Language::English).unwrap();
+    ///
+    /// assert_eq!(mnemonic, Mnemonic::from_phrase(phrase, Language::English).unwrap());
+    /// ```
+    ///
+    /// [Mnemonic]: ../mnemonic/struct.Mnemonic.html
+    /// [Mnemonic::from_phrase()]: ../mnemonic/struct.Mnemonic.html#method.from_phrase
+    pub fn from_phrase_ct(phrase: &str, lang: Language) -> Result<Mnemonic, Error> {
+        Mnemonic::from_phrase_with(phrase, lang, Mnemonic::phrase_to_entropy_ct)
+    }
+
+    fn from_phrase_with<F>(phrase: &str, lang: Language, to_entropy: F) -> Result<Mnemonic, Error>
+    where
+        F: Fn(&str, Language) -> Result<Zeroizing<Vec<u8>>, Error>,
+    {
+        #[cfg(feature = "self-test")]
+        crate::self_test::run_on_first_use()?;
+
+        let phrase = phrase.split_whitespace().map(|w| w.nfkd()).join(" ");
 
         // this also validates the checksum and phrase length before returning the entropy so we
         // can store it. We don't use the validate function here to avoid having a public API that
         // takes a phrase string and returns the entropy directly.
-        let entropy = Zeroizing::new(Mnemonic::phrase_to_entropy(&phrase, lang)?);
+        let entropy = to_entropy(&phrase, lang)?;
 
         let mnemonic = Mnemonic {
-            phrase,
+            phrase: SecretString::from_string(phrase),
             lang,
-            entropy,
+            entropy: SecretBytes::from_vec(entropy),
         };
 
         Ok(mnemonic)
@@ -185,20 +279,68 @@ impl Mnemonic {
         Ok(())
     }
 
+    /// Validate a mnemonic phrase, hardened against timing side channels
+    ///
+    /// See [`Mnemonic::from_phrase_ct()`][Mnemonic::from_phrase_ct()] for what is and isn't
+    /// covered.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bip39::{Mnemonic, Language};
+    ///
+    /// let test_mnemonic = "park remain person kitchen mule spell knee armed position rail grid ankle";
+    ///
+    /// assert!(Mnemonic::validate_ct(test_mnemonic, Language::English).is_ok());
+    /// ```
+    ///
+    /// [Mnemonic::from_phrase_ct()]: ../mnemonic/struct.Mnemonic.html#method.from_phrase_ct
+    pub fn validate_ct(phrase: &str, lang: Language) -> Result<(), Error> {
+        Mnemonic::phrase_to_entropy_ct(phrase, lang)?;
+
+        Ok(())
+    }
+
     /// Calculate the checksum, verify it and return the entropy
     ///
     /// Only intended for internal use, as returning a `Vec<u8>` that looks a bit like it could be
     /// used as the seed is likely to cause problems for someone eventually. All the other functions
     /// that return something like that are explicit about what it is and what to use it for.
-    fn phrase_to_entropy(phrase: &str, lang: Language) -> Result<Vec<u8>, Error> {
-        let wordmap = lang.wordmap();
+    fn phrase_to_entropy(phrase: &str, lang: Language) -> Result<Zeroizing<Vec<u8>>, Error> {
+        let wordmap = lang.try_wordmap()?;
+
+        Mnemonic::words_to_entropy(phrase, |word| wordmap.get_bits(word))
+    }
 
+    /// Same as `phrase_to_entropy`, using the constant time word lookup
+    fn phrase_to_entropy_ct(phrase: &str, lang: Language) -> Result<Zeroizing<Vec<u8>>, Error> {
+        let wordlist = lang.try_wordlist()?;
+
+        Mnemonic::words_to_entropy(phrase, |word| wordlist.get_bits_ct(word).into())
+    }
+
+    fn words_to_entropy<F>(phrase: &str, lookup: F) -> Result<Zeroizing<Vec<u8>>, Error>
+    where
+        F: Fn(&str) -> Option<Bits11>,
+    {
+        Mnemonic::bits_to_entropy(
+            phrase
+                .split(' ')
+                .enumerate()
+                .map(|(idx, word)| lookup(word).ok_or(ErrorKind::InvalidWord(idx))),
+        )
+    }
+
+    /// Pack the 11-bit word values, verify the checksum and return the entropy
+    pub(crate) fn bits_to_entropy<I>(words: I) -> Result<Zeroizing<Vec<u8>>, Error>
+    where
+        I: Iterator<Item = Result<Bits11, ErrorKind>>,
+    {
         // Preallocate enough space for the longest possible word list
         let mut bits = BitWriter::with_capacity(264);
 
-        for (idx, word) in phrase.split(' ').enumerate() {
-            let word_bits = wordmap.get_bits(word).ok_or(ErrorKind::InvalidWord(idx))?;
-            bits.push(word_bits);
+        for word_bits in words {
+            bits.push(word_bits?);
         }
 
         let mtype = MnemonicType::for_word_count(bits.len() / 11)?;
@@ -219,7 +361,7 @@ impl Mnemonic {
         let checksum_byte = sha256_first_byte(&entropy);
         let expected_checksum = checksum(checksum_byte, mtype.checksum_bits());
 
-        if actual_checksum != expected_checksum {
+        if !bool::from(actual_checksum.ct_eq(&expected_checksum)) {
             Err(ErrorKind::InvalidChecksum)?;
         }
 
@@ -228,14 +370,12 @@ impl Mnemonic {
 
     /// Get the mnemonic phrase as a string reference.
     pub fn phrase(&self) -> &str {
-        &self.phrase
+        self.phrase.as_str()
     }
 
-    /// Consume the `Mnemonic` and return the phrase as a `String`.
-    pub fn into_phrase(mut self) -> String {
-        // Create an empty string and swap values with the mnemonic's phrase.
-        // This allows `Mnemonic` to implement `Drop`, while still returning the phrase.
-        mem::replace(&mut self.phrase, String::new())
+    /// Consume the `Mnemonic` and return the phrase as a `String` that is zeroed on drop.
+    pub fn into_phrase(self) -> Zeroizing<String> {
+        self.phrase.into_string()
     }
 
     /// Get the original entropy value of the mnemonic phrase as a slice.
@@ -255,7 +395,100 @@ impl Mnemonic {
     /// **Note:** You shouldn't use the generated entropy as secrets, for that generate a new
     /// `Seed` from the `Mnemonic`.
     pub fn entropy(&self) -> &[u8] {
-        &self.entropy
+        self.entropy.as_bytes()
+    }
+
+    /// Get the position of each word in the word list, from `0` to `2047`
+    ///
+    /// These are the raw 11-bit values the phrase encodes, entropy followed by the checksum.
+    /// Backup plates number the words from 1 to 2048 instead, use
+    /// [`Mnemonic::plate_numbers()`][Mnemonic::plate_numbers()] for those.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bip39::{Mnemonic, Language};
+    ///
+    /// let phrase = "park remain person kitchen mule spell knee armed position rail grid ankle";
+    ///
+    /// let mnemonic = Mnemonic::from_phrase(phrase, Language::English).unwrap();
+    ///
+    /// assert_eq!(mnemonic.word_indices()[..3], [1282, 1452, 1306]);
+    /// ```
+    ///
+    /// [Mnemonic::plate_numbers()]: ../mnemonic/struct.Mnemonic.html#method.plate_numbers
+    pub fn word_indices(&self) -> Zeroizing<Vec<u16>> {
+        let entropy = self.entropy();
+        let checksum_byte = sha256_first_byte(entropy);
+
+        let mut indices = Zeroizing::new(Vec::with_capacity(entropy.len() * 8 * 33 / 32 / 11));
+
+        indices.extend(
+            entropy
+                .iter()
+                .chain(Some(&checksum_byte))
+                .bits()
+                .map(|bits: Bits11| u16::from(bits)),
+        );
+
+        indices
+    }
+
+    /// Create a [`Mnemonic`][Mnemonic] from word list positions, from `0` to `2047`
+    ///
+    /// The count and checksum are validated the same way as for
+    /// [`Mnemonic::from_phrase()`][Mnemonic::from_phrase()]. An index out of range returns an
+    /// `Error` of kind `ErrorKind::InvalidWord` with its position. Numbers read off a backup
+    /// plate count from 1, pass them to
+    /// [`Mnemonic::from_plate_numbers()`][Mnemonic::from_plate_numbers()] instead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bip39::{Mnemonic, Language};
+    ///
+    /// let indices = [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 3];
+    ///
+    /// let mnemonic = Mnemonic::from_word_indices(&indices, Language::English).unwrap();
+    ///
+    /// assert_eq!(mnemonic.phrase(), "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about");
+    /// ```
+    ///
+    /// [Mnemonic]: ../mnemonic/struct.Mnemonic.html
+    /// [Mnemonic::from_phrase()]: ../mnemonic/struct.Mnemonic.html#method.from_phrase
+    /// [Mnemonic::from_plate_numbers()]: ../mnemonic/struct.Mnemonic.html#method.from_plate_numbers
+    pub fn from_word_indices(indices: &[u16], lang: Language) -> Result<Mnemonic, Error> {
+        let entropy = Mnemonic::bits_to_entropy(indices.iter().enumerate().map(|(idx, &index)| {
+            if index < 2048 {
+                Ok(Bits11::from(index))
+            } else {
+                Err(ErrorKind::InvalidWord(idx))
+            }
+        }))?;
+
+        Mnemonic::from_entropy_unchecked(entropy, lang)
+    }
+
+    /// Explicitly format the secret phrase or entropy
+    ///
+    /// The returned [`Exposed`][Exposed] wrapper implements `Display` for the phrase, and
+    /// `LowerHex`/`UpperHex` for the entropy.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bip39::{Mnemonic, Language};
+    ///
+    /// let phrase = "park remain person kitchen mule spell knee armed position rail grid ankle";
+    ///
+    /// let mnemonic = Mnemonic::from_phrase(phrase, Language::English).unwrap();
+    ///
+    /// assert_eq!(format!("{}", mnemonic.expose()), phrase);
+    /// ```
+    ///
+    /// [Exposed]: ../exposed/struct.Exposed.html
+    pub fn expose(&self) -> Exposed<'_, Mnemonic> {
+        Exposed(self)
     }
 
     /// Get the [`Language`][Language]
@@ -264,6 +497,20 @@ impl Mnemonic {
     pub fn language(&self) -> Language {
         self.lang
     }
+
+    /// Whether the phrase and entropy are held in locked memory
+    ///
+    /// Anything other than [`MemoryLock::Locked`][MemoryLock] means at least one of them fell
+    /// back to the regular heap.
+    ///
+    /// [MemoryLock]: ../secret/enum.MemoryLock.html
+    #[cfg(feature = "mlock")]
+    pub fn memory_lock(&self) -> MemoryLock {
+        match self.phrase.memory_lock() {
+            MemoryLock::Locked => self.entropy.memory_lock(),
+            lock => lock,
+        }
+    }
 }
 
 impl AsRef<str> for Mnemonic {
@@ -272,48 +519,32 @@ impl AsRef<str> for Mnemonic {
     }
 }
 
-impl fmt::Display for Mnemonic {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        fmt::Display::fmt(self.phrase(), f)
+/// Compares the entropy in constant time
+impl ConstantTimeEq for Mnemonic {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        Choice::from((self.lang == other.lang) as u8) & self.entropy().ct_eq(other.entropy())
     }
 }
 
-impl fmt::Debug for Mnemonic {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        fmt::Debug::fmt(self.phrase(), f)
+impl PartialEq for Mnemonic {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
     }
 }
 
-impl fmt::LowerHex for Mnemonic {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        if f.alternate() {
-            f.write_str("0x")?;
-        }
-
-        for byte in self.entropy() {
-            write!(f, "{:02x}", byte)?;
-        }
-
-        Ok(())
-    }
-}
+impl Eq for Mnemonic {}
 
-impl fmt::UpperHex for Mnemonic {
+impl fmt::Debug for Mnemonic {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        if f.alternate() {
-            f.write_str("0x")?;
-        }
-
-        for byte in self.entropy() {
-            write!(f, "{:02X}", byte)?;
-        }
-
-        Ok(())
+        f.debug_struct("Mnemonic")
+            .field("language", &self.lang)
+            .field("words", &self.phrase().split(' ').count())
+            .finish_non_exhaustive()
     }
 }
 
-impl From<Mnemonic> for String {
-    fn from(val: Mnemonic) -> String {
+impl From<Mnemonic> for Zeroizing<String> {
+    fn from(val: Mnemonic) -> Zeroizing<String> {
         val.into_phrase()
     }
 }
@@ -337,6 +568,39 @@ mod test {
         assert_eq!(m1.phrase(), m3.phrase(), "Phrase must be the same");
     }
 
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn word_indices_round_trip() {
+        for mtype in &[MnemonicType::Words12, MnemonicType::Words18, MnemonicType::Words24] {
+            let m1 = Mnemonic::new(*mtype, Language::English);
+            let indices = m1.word_indices();
+            let m2 = Mnemonic::from_word_indices(&indices, Language::English).unwrap();
+
+            assert_eq!(indices.len(), mtype.word_count());
+            assert_eq!(m1, m2);
+        }
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn word_indices_invalid() {
+        let mut indices = [0u16; 12];
+
+        assert!(Mnemonic::from_word_indices(&indices, Language::English).is_err());
+
+        indices[11] = 3;
+        assert!(Mnemonic::from_word_indices(&indices, Language::English).is_ok());
+
+        indices[5] = 2048;
+        match Mnemonic::from_word_indices(&indices, Language::English) {
+            Err(err) => match err.downcast_ref::<ErrorKind>() {
+                Some(ErrorKind::InvalidWord(5)) => (),
+                _ => panic!("expected InvalidWord(5), got {:?}", err),
+            },
+            Ok(_) => panic!("index out of range was accepted"),
+        }
+    }
+
     #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
     #[cfg_attr(not(target_arch = "wasm32"), test)]
     fn mnemonic_from_entropy() {
@@ -365,12 +629,55 @@ mod test {
         assert_eq!(entropy, mnemonic.entropy());
     }
 
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn mnemonic_from_phrase_ct() {
+        let phrase = "crop cash unable insane eight faith inflict route frame loud box vibrant";
+
+        let mnemonic = Mnemonic::from_phrase_ct(phrase, Language::English).unwrap();
+
+        assert_eq!(
+            mnemonic,
+            Mnemonic::from_phrase(phrase, Language::English).unwrap()
+        );
+        assert!(
+            Mnemonic::validate_ct(&phrase.replace("vibrant", "zoo"), Language::English).is_err()
+        );
+        assert!(
+            Mnemonic::validate_ct(&phrase.replace("crop", "crops"), Language::English).is_err()
+        );
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn mnemonic_eq() {
+        let m1 = Mnemonic::from_entropy(&[0x11; 16], Language::English).unwrap();
+        let m2 = Mnemonic::from_entropy(&[0x11; 16], Language::English).unwrap();
+        let m3 = Mnemonic::from_entropy(&[0x12; 16], Language::English).unwrap();
+        let m4 = Mnemonic::from_entropy(&[0x11; 32], Language::English).unwrap();
+
+        assert_eq!(m1, m2);
+        assert_ne!(m1, m3);
+        assert_ne!(m1, m4);
+    }
+
     #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
     #[cfg_attr(not(target_arch = "wasm32"), test)]
     fn mnemonic_format() {
         let mnemonic = Mnemonic::new(MnemonicType::Words15, Language::English);
 
-        assert_eq!(mnemonic.phrase(), format!("{}", mnemonic));
+        assert_eq!(mnemonic.phrase(), format!("{}", mnemonic.expose()));
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn mnemonic_debug_is_redacted() {
+        let mnemonic = Mnemonic::from_entropy(&[0xFF; 32], Language::English).unwrap();
+
+        assert_eq!(
+            format!("{:?}", mnemonic),
+            "Mnemonic { language: English, words: 24, .. }"
+        );
     }
 
     #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
@@ -384,19 +691,19 @@ mod test {
         let mnemonic = Mnemonic::from_entropy(entropy, Language::English).unwrap();
 
         assert_eq!(
-            format!("{:x}", mnemonic),
+            format!("{:x}", mnemonic.expose()),
             "03e46bb13a746ea41cdde45c90846a79"
         );
         assert_eq!(
-            format!("{:X}", mnemonic),
+            format!("{:X}", mnemonic.expose()),
             "03E46BB13A746EA41CDDE45C90846A79"
         );
         assert_eq!(
-            format!("{:#x}", mnemonic),
+            format!("{:#x}", mnemonic.expose()),
             "0x03e46bb13a746ea41cdde45c90846a79"
         );
         assert_eq!(
-            format!("{:#X}", mnemonic),
+            format!("{:#X}", mnemonic.expose()),
             "0x03E46BB13A746EA41CDDE45C90846A79"
         );
     }
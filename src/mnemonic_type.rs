@@ -29,9 +29,10 @@ const ENTROPY_OFFSET: usize = 8;
 /// [Mnemonic]: ../mnemonic/struct.Mnemonic.html
 /// [Seed]: ../seed/struct.Seed.html
 ///
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, Default)]
 pub enum MnemonicType {
     //  ... = (entropy_bits << ...)   | checksum_bits
+    #[default]
     Words12 = (128 << ENTROPY_OFFSET) | 4,
     Words15 = (160 << ENTROPY_OFFSET) | 5,
     Words18 = (192 << ENTROPY_OFFSET) | 6,
@@ -182,12 +183,6 @@ impl MnemonicType {
     }
 }
 
-impl Default for MnemonicType {
-    fn default() -> MnemonicType {
-        MnemonicType::Words12
-    }
-}
-
 impl fmt::Display for MnemonicType {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
@@ -0,0 +1,427 @@
+//! Passphrase hygiene checks.
+//!
+//! BIP39 accepts any passphrase, and every passphrase leads to a valid wallet, so a passphrase
+//! that is typed slightly differently later on silently opens a different, empty wallet. The
+//! usual causes are stray whitespace, characters that NFKD normalization changes, characters
+//! that look like others, and keys that are somewhere else on another keyboard layout.
+//! [`analyze()`][analyze()] reports all of these, along with a rough strength estimate.
+//!
+//! ```
+//! use bip39::passphrase::{self, WhitespaceIssue};
+//!
+//! let report = passphrase::analyze(" ﬁsh tank");
+//!
+//! assert_eq!(report.normalized(), " fish tank");
+//! assert!(report.is_normalization_changed());
+//! assert_eq!(report.whitespace_issues(), [WhitespaceIssue::Leading]);
+//! ```
+//!
+//! Positions in the report count characters of the passphrase as given, from `0`.
+//!
+//! [analyze()]: ./fn.analyze.html
+
+use std::fmt;
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+use zeroize::Zeroizing;
+
+/// Cyrillic and Greek letters that look the same as a Latin letter
+const HOMOGLYPHS: &str = concat!(
+    "АΑ", // A
+    "ВΒ", // B
+    "С",  // C
+    "ЕΕ", // E
+    "НΗ", // H
+    "ІΙ", // I
+    "Ј",  // J
+    "КΚ", // K
+    "МΜ", // M
+    "Ν",  // N
+    "ОΟ", // O
+    "РΡ", // P
+    "Ѕ",  // S
+    "ТΤ", // T
+    "ХΧ", // X
+    "УΥ", // Y
+    "Ζ",  // Z
+    "а",  // a
+    "с",  // c
+    "ԁ",  // d
+    "е",  // e
+    "һ",  // h
+    "іι", // i
+    "ј",  // j
+    "оο", // o
+    "рρ", // p
+    "ѕ",  // s
+    "ν",  // v
+    "х",  // x
+    "у",  // y
+);
+
+/// Letters that are on different keys in the US QWERTY, German QWERTZ or French AZERTY layouts
+const LAYOUT_SENSITIVE_LETTERS: &str = "amqwyz";
+
+/// A whitespace problem in a passphrase
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WhitespaceIssue {
+    /// The passphrase starts with whitespace
+    Leading,
+    /// The passphrase ends with whitespace
+    Trailing,
+    /// Two or more whitespace characters in a row
+    Repeated,
+    /// Whitespace other than a plain space, such as a tab, a newline or a no-break space
+    Unusual,
+}
+
+/// The writing systems a passphrase is detected to use, see
+/// [`PassphraseReport::scripts()`][scripts]
+///
+/// [scripts]: ./struct.PassphraseReport.html#method.scripts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Script {
+    Latin,
+    Greek,
+    Cyrillic,
+    Han,
+    Kana,
+    Hangul,
+    /// Letters of any other script
+    Other,
+}
+
+/// The result of [`analyze()`][analyze()]
+///
+/// The `Debug` output leaves out the normalized passphrase.
+///
+/// [analyze()]: ./fn.analyze.html
+#[derive(Clone)]
+pub struct PassphraseReport {
+    normalized: Zeroizing<String>,
+    normalization_changed: bool,
+    entropy_bits: f64,
+    whitespace_issues: Vec<WhitespaceIssue>,
+    scripts: Vec<Script>,
+    confusables: Vec<usize>,
+    layout_sensitive: Vec<usize>,
+}
+
+impl PassphraseReport {
+    /// The NFKD normalized passphrase, which is what [`Seed::new()`][Seed::new()] actually uses
+    ///
+    /// [Seed::new()]: ../seed/struct.Seed.html#method.new
+    pub fn normalized(&self) -> &str {
+        &self.normalized
+    }
+
+    /// Whether normalization changed the passphrase, in which case typing
+    /// [`normalized()`][normalized()] gives the same wallet
+    ///
+    /// [normalized()]: ./struct.PassphraseReport.html#method.normalized
+    pub fn is_normalization_changed(&self) -> bool {
+        self.normalization_changed
+    }
+
+    /// A rough estimate of the strength in bits, assuming the characters were picked at random
+    /// from the classes that appear in it
+    ///
+    /// Real passphrases are made of words and patterns, so this is an upper bound, and a poor one
+    /// for anything but random strings.
+    pub fn entropy_bits(&self) -> f64 {
+        self.entropy_bits
+    }
+
+    /// Whitespace problems, in the order they are listed in
+    /// [`WhitespaceIssue`][WhitespaceIssue]
+    ///
+    /// [WhitespaceIssue]: ./enum.WhitespaceIssue.html
+    pub fn whitespace_issues(&self) -> &[WhitespaceIssue] {
+        &self.whitespace_issues
+    }
+
+    /// The scripts of the letters in the passphrase, sorted
+    pub fn scripts(&self) -> &[Script] {
+        &self.scripts
+    }
+
+    /// Whether letters from more than one script are used, not counting Japanese Han and Kana
+    pub fn is_mixed_script(&self) -> bool {
+        let japanese = self.scripts.contains(&Script::Han);
+
+        self.scripts
+            .iter()
+            .filter(|&&script| !(japanese && script == Script::Kana))
+            .count()
+            > 1
+    }
+
+    /// Positions of invisible characters, and of Cyrillic or Greek letters that look like a Latin
+    /// letter in a passphrase that also uses Latin letters, like the `а` in `pаssword`
+    pub fn confusables(&self) -> &[usize] {
+        &self.confusables
+    }
+
+    /// Positions of characters that are typed with a different key, or need a different input
+    /// method, on another common keyboard layout
+    ///
+    /// Only space and the Latin letters that are in the same place on US QWERTY, German QWERTZ
+    /// and French AZERTY keyboards are considered safe. A passphrase with many of these
+    /// characters can be hard to enter on a device with an unfamiliar keyboard.
+    pub fn layout_sensitive(&self) -> &[usize] {
+        &self.layout_sensitive
+    }
+}
+
+impl fmt::Debug for PassphraseReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("PassphraseReport")
+            .field("normalization_changed", &self.normalization_changed)
+            .field("entropy_bits", &self.entropy_bits)
+            .field("whitespace_issues", &self.whitespace_issues)
+            .field("scripts", &self.scripts)
+            .field("confusables", &self.confusables)
+            .field("layout_sensitive", &self.layout_sensitive)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Check a passphrase before it is used to create a [`Seed`][Seed]
+///
+/// # Example
+///
+/// ```
+/// use bip39::passphrase::{self, Script};
+///
+/// // The second letter is a Cyrillic "а"
+/// let report = passphrase::analyze("pаssword");
+///
+/// assert!(report.is_mixed_script());
+/// assert_eq!(report.scripts(), [Script::Latin, Script::Cyrillic]);
+/// assert_eq!(report.confusables(), [1]);
+/// ```
+///
+/// [Seed]: ../seed/struct.Seed.html
+pub fn analyze(passphrase: &str) -> PassphraseReport {
+    let normalized: Zeroizing<String> = Zeroizing::new(passphrase.nfkd().collect());
+    let normalization_changed = *normalized != passphrase;
+
+    let mut whitespace_issues = Vec::new();
+
+    if passphrase.starts_with(char::is_whitespace) {
+        whitespace_issues.push(WhitespaceIssue::Leading);
+    }
+
+    if passphrase.ends_with(char::is_whitespace) {
+        whitespace_issues.push(WhitespaceIssue::Trailing);
+    }
+
+    let mut previous = None;
+
+    if passphrase.chars().any(|ch| {
+        let repeated = ch.is_whitespace() && previous.is_some_and(char::is_whitespace);
+
+        previous = Some(ch);
+        repeated
+    }) {
+        whitespace_issues.push(WhitespaceIssue::Repeated);
+    }
+
+    if passphrase.chars().any(|ch| ch.is_whitespace() && ch != ' ') {
+        whitespace_issues.push(WhitespaceIssue::Unusual);
+    }
+
+    let mut scripts: Vec<Script> = passphrase.chars().filter_map(script).collect();
+
+    scripts.sort_unstable();
+    scripts.dedup();
+
+    let has_latin = scripts.contains(&Script::Latin);
+
+    let confusables = passphrase
+        .chars()
+        .enumerate()
+        .filter(|&(_, ch)| is_invisible(ch) || has_latin && HOMOGLYPHS.contains(ch))
+        .map(|(position, _)| position)
+        .collect();
+
+    let layout_sensitive = passphrase
+        .chars()
+        .enumerate()
+        .filter(|&(_, ch)| {
+            !(ch == ' '
+                || ch.is_ascii_alphabetic()
+                    && !LAYOUT_SENSITIVE_LETTERS.contains(ch.to_ascii_lowercase()))
+        })
+        .map(|(position, _)| position)
+        .collect();
+
+    PassphraseReport {
+        normalized,
+        normalization_changed,
+        entropy_bits: entropy_bits(passphrase),
+        whitespace_issues,
+        scripts,
+        confusables,
+        layout_sensitive,
+    }
+}
+
+/// Character count times the bits per character of the classes used, not counting a character
+/// that repeats the one before it
+fn entropy_bits(passphrase: &str) -> f64 {
+    let (mut lower, mut upper, mut digit, mut symbol, mut other) = (0, 0, 0, 0, 0);
+    let mut count = 0;
+    let mut previous = None;
+
+    for ch in passphrase.chars() {
+        match ch {
+            'a'..='z' => lower = 26,
+            'A'..='Z' => upper = 26,
+            '0'..='9' => digit = 10,
+            ' '..='~' => symbol = 33,
+            _ => other = 100,
+        }
+
+        if previous != Some(ch) {
+            count += 1;
+        }
+
+        previous = Some(ch);
+    }
+
+    let pool = lower + upper + digit + symbol + other;
+
+    if pool == 0 {
+        return 0.0;
+    }
+
+    count as f64 * f64::from(pool).log2()
+}
+
+/// The script of a letter, rough ranges from the Unicode blocks
+fn script(ch: char) -> Option<Script> {
+    if !ch.is_alphabetic() || is_combining_mark(ch) {
+        return None;
+    }
+
+    let script = match ch as u32 {
+        0x0000..=0x024F | 0x1E00..=0x1EFF | 0xFF21..=0xFF5A => Script::Latin,
+        0x0370..=0x03FF | 0x1F00..=0x1FFF => Script::Greek,
+        0x0400..=0x052F => Script::Cyrillic,
+        0x3040..=0x30FF | 0xFF66..=0xFF9F => Script::Kana,
+        0x3400..=0x4DBF | 0x4E00..=0x9FFF | 0xF900..=0xFAFF => Script::Han,
+        0x1100..=0x11FF | 0x3130..=0x318F | 0xAC00..=0xD7AF => Script::Hangul,
+        _ => Script::Other,
+    };
+
+    Some(script)
+}
+
+/// Characters that take up no space, or change the direction of the text around them
+fn is_invisible(ch: char) -> bool {
+    matches!(
+        ch,
+        '\u{AD}'
+            | '\u{200B}'..='\u{200F}'
+            | '\u{202A}'..='\u{202E}'
+            | '\u{2060}'..='\u{2064}'
+            | '\u{FEFF}'
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn clean_passphrase() {
+        let report = analyze("correct horse battery staple");
+
+        assert_eq!(report.normalized(), "correct horse battery staple");
+        assert!(!report.is_normalization_changed());
+        assert!(report.whitespace_issues().is_empty());
+        assert_eq!(report.scripts(), [Script::Latin]);
+        assert!(!report.is_mixed_script());
+        assert!(report.confusables().is_empty());
+        // The "a" and "y" of "battery staple"
+        assert_eq!(report.layout_sensitive(), [15, 20, 24]);
+        // 28 characters with "rr" and "tt" counted once, from lower case letters and symbols
+        assert!((report.entropy_bits() - 26.0 * 59f64.log2()).abs() < 1e-9);
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn whitespace() {
+        let report = analyze("\tone  two\u{A0}");
+
+        assert_eq!(
+            report.whitespace_issues(),
+            [
+                WhitespaceIssue::Leading,
+                WhitespaceIssue::Trailing,
+                WhitespaceIssue::Repeated,
+                WhitespaceIssue::Unusual,
+            ]
+        );
+        assert_eq!(report.normalized(), "\tone  two ");
+        assert!(report.is_normalization_changed());
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn normalization() {
+        let report = analyze("Ｃafé");
+
+        assert_eq!(report.normalized(), "Cafe\u{301}");
+        assert!(report.is_normalization_changed());
+        assert_eq!(report.scripts(), [Script::Latin]);
+        assert_eq!(report.layout_sensitive(), [0, 1, 3]);
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn confusables() {
+        // Latin with a Greek omicron, and a zero width space
+        let report = analyze("bοok\u{200B}");
+
+        assert_eq!(report.scripts(), [Script::Latin, Script::Greek]);
+        assert!(report.is_mixed_script());
+        assert_eq!(report.confusables(), [1, 4]);
+
+        // Cyrillic only, nothing to confuse it with
+        let report = analyze("пароль");
+
+        assert_eq!(report.scripts(), [Script::Cyrillic]);
+        assert!(!report.is_mixed_script());
+        assert!(report.confusables().is_empty());
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn japanese_is_not_mixed() {
+        let report = analyze("ひらがなと漢字");
+
+        assert_eq!(report.scripts(), [Script::Han, Script::Kana]);
+        assert!(!report.is_mixed_script());
+        assert!(analyze("かなとlatin").is_mixed_script());
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn empty_passphrase() {
+        let report = analyze("");
+
+        assert_eq!(report.entropy_bits(), 0.0);
+        assert!(report.whitespace_issues().is_empty());
+        assert!(report.scripts().is_empty());
+        assert_eq!(
+            format!("{:?}", report),
+            "PassphraseReport { normalization_changed: false, entropy_bits: 0.0, \
+             whitespace_issues: [], scripts: [], confusables: [], layout_sensitive: [], .. }"
+        );
+    }
+}
@@ -0,0 +1,342 @@
+//! Storage for the secret bytes behind [`Mnemonic`][Mnemonic] and [`Seed`][Seed].
+//!
+//! By default secrets live on the heap and are zeroed on drop. With the `mlock` feature enabled
+//! on Unix, each secret is instead placed in its own anonymous memory mapping, surrounded by
+//! inaccessible guard pages, locked into RAM so it can't be swapped out, and (on Linux) excluded
+//! from core dumps. When locking fails, typically because `RLIMIT_MEMLOCK` has been reached, the
+//! secret falls back to the heap, and [`MemoryLock`][MemoryLock] reports what happened.
+//!
+//! [Mnemonic]: ../mnemonic/struct.Mnemonic.html
+//! [Seed]: ../seed/struct.Seed.html
+//! [MemoryLock]: ./enum.MemoryLock.html
+
+use std::mem;
+use zeroize::{Zeroize, Zeroizing};
+
+/// Whether the memory holding a secret is locked, see
+/// [`Mnemonic::memory_lock()`][Mnemonic::memory_lock()] and [`Seed::memory_lock()`][Seed::memory_lock()].
+///
+/// [Mnemonic::memory_lock()]: ./mnemonic/struct.Mnemonic.html#method.memory_lock
+/// [Seed::memory_lock()]: ./seed/struct.Seed.html#method.memory_lock
+#[cfg(feature = "mlock")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemoryLock {
+    /// The secret is in locked memory, protected by guard pages and excluded from core dumps
+    Locked,
+    /// The process hit its `RLIMIT_MEMLOCK` limit (or lacks the privilege to lock memory), the
+    /// secret is stored on the regular heap instead
+    LimitReached,
+    /// Locked memory is not available on this platform, or allocating it failed for another
+    /// reason, the secret is stored on the regular heap instead
+    Unavailable,
+}
+
+pub(crate) struct SecretBytes {
+    storage: Storage,
+    #[cfg(feature = "mlock")]
+    lock: MemoryLock,
+}
+
+enum Storage {
+    Heap(Zeroizing<Vec<u8>>),
+    #[cfg(all(feature = "mlock", unix))]
+    Locked(locked::Region),
+}
+
+impl SecretBytes {
+    /// Take ownership of the secret, moving it to locked memory if enabled
+    pub fn from_vec(bytes: Zeroizing<Vec<u8>>) -> Self {
+        #[cfg(feature = "mlock")]
+        {
+            let mut secret = Self::zeroed(bytes.len());
+
+            if let Storage::Heap(_) = secret.storage {
+                // Locking failed, reuse the existing allocation rather than copying it
+                secret.storage = Storage::Heap(bytes);
+            } else {
+                secret.as_bytes_mut().copy_from_slice(&bytes);
+            }
+
+            secret
+        }
+
+        #[cfg(not(feature = "mlock"))]
+        SecretBytes {
+            storage: Storage::Heap(bytes),
+        }
+    }
+
+    /// Allocate `len` zeroed bytes, to be filled in place
+    pub fn zeroed(len: usize) -> Self {
+        #[cfg(all(feature = "mlock", unix))]
+        {
+            match locked::Region::new(len) {
+                Ok(region) => SecretBytes {
+                    storage: Storage::Locked(region),
+                    lock: MemoryLock::Locked,
+                },
+                Err(lock) => SecretBytes {
+                    storage: Storage::Heap(Zeroizing::new(vec![0; len])),
+                    lock,
+                },
+            }
+        }
+
+        #[cfg(not(all(feature = "mlock", unix)))]
+        SecretBytes {
+            storage: Storage::Heap(Zeroizing::new(vec![0; len])),
+            #[cfg(feature = "mlock")]
+            lock: MemoryLock::Unavailable,
+        }
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        match self.storage {
+            Storage::Heap(ref bytes) => bytes,
+            #[cfg(all(feature = "mlock", unix))]
+            Storage::Locked(ref region) => region.as_slice(),
+        }
+    }
+
+    pub fn as_bytes_mut(&mut self) -> &mut [u8] {
+        match self.storage {
+            Storage::Heap(ref mut bytes) => bytes,
+            #[cfg(all(feature = "mlock", unix))]
+            Storage::Locked(ref mut region) => region.as_mut_slice(),
+        }
+    }
+
+    /// Move the secret out to the heap, copying only if it was in locked memory
+    pub fn into_vec(mut self) -> Zeroizing<Vec<u8>> {
+        match self.storage {
+            Storage::Heap(ref mut bytes) => Zeroizing::new(mem::take(&mut **bytes)),
+            #[cfg(all(feature = "mlock", unix))]
+            Storage::Locked(ref region) => Zeroizing::new(region.as_slice().to_vec()),
+        }
+    }
+
+    #[cfg(feature = "mlock")]
+    pub fn memory_lock(&self) -> MemoryLock {
+        self.lock
+    }
+}
+
+impl Clone for SecretBytes {
+    fn clone(&self) -> Self {
+        let mut secret = Self::zeroed(self.as_bytes().len());
+
+        secret.as_bytes_mut().copy_from_slice(self.as_bytes());
+        secret
+    }
+}
+
+impl Zeroize for SecretBytes {
+    fn zeroize(&mut self) {
+        self.as_bytes_mut().zeroize();
+    }
+}
+
+/// UTF-8 secret, only ever constructed from a `String`
+#[derive(Clone)]
+pub(crate) struct SecretString(SecretBytes);
+
+impl SecretString {
+    pub fn from_string(mut string: Zeroizing<String>) -> Self {
+        let bytes = Zeroizing::new(mem::take(&mut *string).into_bytes());
+
+        SecretString(SecretBytes::from_vec(bytes))
+    }
+
+    pub fn as_str(&self) -> &str {
+        // SAFETY: the bytes were copied from a `String` and are never mutated
+        unsafe { std::str::from_utf8_unchecked(self.0.as_bytes()) }
+    }
+
+    pub fn into_string(self) -> Zeroizing<String> {
+        let mut bytes = self.0.into_vec();
+
+        // SAFETY: see `as_str`
+        Zeroizing::new(unsafe { String::from_utf8_unchecked(mem::take(&mut *bytes)) })
+    }
+
+    #[cfg(feature = "mlock")]
+    pub fn memory_lock(&self) -> MemoryLock {
+        self.0.memory_lock()
+    }
+}
+
+#[cfg(all(feature = "mlock", unix))]
+mod locked {
+    use super::MemoryLock;
+    use std::{io, ptr, slice};
+    use zeroize::Zeroize;
+
+    /// Anonymous mapping laid out as `[guard page][data pages][guard page]`, with the secret
+    /// placed at the very end of the data pages so that overruns fault immediately.
+    pub struct Region {
+        base: *mut u8,
+        total: usize,
+        pages: *mut u8,
+        pages_len: usize,
+        len: usize,
+    }
+
+    // The region is uniquely owned, just like a `Vec<u8>`
+    unsafe impl Send for Region {}
+    unsafe impl Sync for Region {}
+
+    impl Region {
+        pub fn new(len: usize) -> Result<Region, MemoryLock> {
+            let page = page_size();
+            let pages_len = len.div_ceil(page).max(1) * page;
+            let total = pages_len + 2 * page;
+
+            unsafe {
+                let base = libc::mmap(
+                    ptr::null_mut(),
+                    total,
+                    libc::PROT_NONE,
+                    libc::MAP_PRIVATE | libc::MAP_ANON,
+                    -1,
+                    0,
+                );
+
+                if base == libc::MAP_FAILED {
+                    return Err(MemoryLock::Unavailable);
+                }
+
+                let base = base as *mut u8;
+                let pages = base.add(page);
+
+                if libc::mprotect(
+                    pages as *mut libc::c_void,
+                    pages_len,
+                    libc::PROT_READ | libc::PROT_WRITE,
+                ) != 0
+                {
+                    libc::munmap(base as *mut libc::c_void, total);
+
+                    return Err(MemoryLock::Unavailable);
+                }
+
+                // From here on dropping the region cleans up after us
+                let region = Region {
+                    base,
+                    total,
+                    pages,
+                    pages_len,
+                    len,
+                };
+
+                #[cfg(target_os = "linux")]
+                {
+                    if libc::madvise(pages as *mut libc::c_void, pages_len, libc::MADV_DONTDUMP)
+                        != 0
+                    {
+                        return Err(MemoryLock::Unavailable);
+                    }
+                }
+
+                if libc::mlock(pages as *const libc::c_void, pages_len) != 0 {
+                    return Err(classify(io::Error::last_os_error()));
+                }
+
+                Ok(region)
+            }
+        }
+
+        pub fn as_slice(&self) -> &[u8] {
+            unsafe { slice::from_raw_parts(self.data(), self.len) }
+        }
+
+        pub fn as_mut_slice(&mut self) -> &mut [u8] {
+            unsafe { slice::from_raw_parts_mut(self.data(), self.len) }
+        }
+
+        fn data(&self) -> *mut u8 {
+            unsafe { self.pages.add(self.pages_len - self.len) }
+        }
+    }
+
+    impl Drop for Region {
+        fn drop(&mut self) {
+            unsafe {
+                slice::from_raw_parts_mut(self.pages, self.pages_len).zeroize();
+
+                // munlock fails harmlessly if the pages never got locked
+                libc::munlock(self.pages as *const libc::c_void, self.pages_len);
+                libc::munmap(self.base as *mut libc::c_void, self.total);
+            }
+        }
+    }
+
+    fn page_size() -> usize {
+        match unsafe { libc::sysconf(libc::_SC_PAGESIZE) } {
+            size if size > 0 => size as usize,
+            _ => 4096,
+        }
+    }
+
+    /// `mlock` fails with `ENOMEM` or `EAGAIN` when over `RLIMIT_MEMLOCK`, and with `EPERM`
+    /// when the limit is zero and the process is unprivileged
+    pub(super) fn classify(err: io::Error) -> MemoryLock {
+        match err.raw_os_error() {
+            Some(libc::ENOMEM) | Some(libc::EAGAIN) | Some(libc::EPERM) => MemoryLock::LimitReached,
+            _ => MemoryLock::Unavailable,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn secret_bytes_round_trip() {
+        let secret = SecretBytes::from_vec(Zeroizing::new(vec![1, 2, 3]));
+        let clone = secret.clone();
+
+        assert_eq!(secret.as_bytes(), [1, 2, 3]);
+        assert_eq!(*clone.into_vec(), [1, 2, 3]);
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn secret_string_round_trip() {
+        let secret = SecretString::from_string(Zeroizing::new("zoo zoo wrong".into()));
+
+        assert_eq!(secret.as_str(), "zoo zoo wrong");
+        assert_eq!(*secret.into_string(), "zoo zoo wrong");
+    }
+
+    #[test]
+    #[cfg(all(feature = "mlock", unix))]
+    fn locked_region() {
+        // Big enough to span several pages
+        let mut secret = SecretBytes::zeroed(10_000);
+
+        assert_ne!(secret.memory_lock(), MemoryLock::Unavailable);
+        assert!(secret.as_bytes().iter().all(|&b| b == 0));
+
+        secret.as_bytes_mut()[9_999] = 0xFF;
+        assert_eq!(secret.clone().as_bytes()[9_999], 0xFF);
+    }
+
+    #[test]
+    #[cfg(all(feature = "mlock", unix))]
+    fn lock_limit_is_reported() {
+        use std::io::Error;
+
+        assert_eq!(
+            locked::classify(Error::from_raw_os_error(libc::ENOMEM)),
+            MemoryLock::LimitReached
+        );
+        assert_eq!(
+            locked::classify(Error::from_raw_os_error(libc::EINVAL)),
+            MemoryLock::Unavailable
+        );
+    }
+}
@@ -1,8 +1,14 @@
 use std::fmt;
+use subtle::{Choice, ConstantTimeEq};
 use unicode_normalization::UnicodeNormalization;
 use zeroize::{Zeroize, Zeroizing};
-use crate::crypto::pbkdf2;
+use crate::crypto::{pbkdf2, PBKDF2_BYTES};
+use crate::exposed::Exposed;
 use crate::mnemonic::Mnemonic;
+#[cfg(feature = "mlock")]
+use crate::secret::MemoryLock;
+use crate::secret::SecretBytes;
+use crate::util::IterExt;
 
 /// The secret value used to derive HD wallet addresses from a [`Mnemonic`][Mnemonic] phrase.
 ///
@@ -16,71 +22,416 @@ use crate::mnemonic::Mnemonic;
 ///
 /// [`Seed`][Seed] implements [`Zeroize`][Zeroize], so it's bytes will be zeroed when it's dropped.
 ///
+/// The `Debug` output is redacted, use [`Seed::expose()`][Seed::expose()] to format the seed as hex.
+///
 /// [Mnemonic]: ./mnemonic/struct.Mnemonic.html
 /// [Seed]: ./seed/struct.Seed.html
 /// [Seed::as_bytes()]: ./seed/struct.Seed.html#method.as_bytes
+/// [Seed::expose()]: ./seed/struct.Seed.html#method.expose
 
 #[derive(Clone, Zeroize)]
 #[zeroize(drop)]
 pub struct Seed {
-    bytes: Vec<u8>,
+    bytes: SecretBytes,
 }
 
 impl Seed {
     /// Generates the seed from the [`Mnemonic`][Mnemonic] and the password.
     ///
+    /// # Panics
+    ///
+    /// With the `self-test` feature, if the [self test][self_test()] fails. This can only happen
+    /// if no `Mnemonic` has been created yet, as that runs the self test too.
+    ///
     /// [Mnemonic]: ./mnemonic/struct.Mnemonic.html
+    /// [self_test()]: ./fn.self_test.html
     pub fn new(mnemonic: &Mnemonic, password: &str) -> Self {
-        let salt = Zeroizing::new(format!("mnemonic{}", password));
-        let normalized_salt = Zeroizing::new(salt.nfkd().to_string());
-        let bytes = pbkdf2(mnemonic.phrase().as_bytes(), &normalized_salt);
+        #[cfg(feature = "self-test")]
+        crate::self_test::assert_on_first_use();
+
+        Seed::derive(mnemonic.phrase(), password)
+    }
+
+    /// Derive the seed from a phrase that is already normalized and validated
+    pub(crate) fn derive(phrase: &str, password: &str) -> Self {
+        Seed::from_inputs(phrase.as_bytes(), &salt(password))
+    }
+
+    fn from_inputs(phrase: &[u8], salt: &SecretBytes) -> Self {
+        let mut seed = Self {
+            bytes: SecretBytes::zeroed(PBKDF2_BYTES),
+        };
+
+        pbkdf2(phrase, salt.as_bytes(), seed.bytes.as_bytes_mut());
+
+        seed
+    }
+
+    /// Generates the seed from a phrase and password the way some wallets did, without following
+    /// BIP39 to the letter
+    ///
+    /// This is only meant for recovering funds from such wallets, use
+    /// [`Seed::new()`][Seed::new()] for anything else. The phrase is **not** validated, so phrases
+    /// with an invalid checksum, or words missing from the word list, are accepted too. With the
+    /// default [`SeedOptions`][SeedOptions] the result is the same as with
+    /// [`Seed::new()`][Seed::new()] for any valid phrase.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bip39::{Mnemonic, Language, Normalization, Seed, SeedOptions};
+    ///
+    /// let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+    /// let mnemonic = Mnemonic::from_phrase(phrase, Language::English).unwrap();
+    ///
+    /// let bip39 = Seed::new_with_options(phrase, "café", SeedOptions::default());
+    /// let nfc = Seed::new_with_options(phrase, "café", SeedOptions {
+    ///     password_normalization: Normalization::Nfc,
+    ///     ..SeedOptions::default()
+    /// });
+    ///
+    /// assert_eq!(bip39, Seed::new(&mnemonic, "café"));
+    /// assert_ne!(bip39, nfc);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// With the `self-test` feature, if the [self test][self_test()] fails.
+    ///
+    /// [Seed::new()]: ./seed/struct.Seed.html#method.new
+    /// [SeedOptions]: ./seed/struct.SeedOptions.html
+    /// [self_test()]: ./fn.self_test.html
+    pub fn new_with_options(phrase: &str, password: &str, options: SeedOptions) -> Self {
+        #[cfg(feature = "self-test")]
+        crate::self_test::assert_on_first_use();
+
+        let (phrase, salt) = options.inputs(phrase, password);
+
+        Seed::from_inputs(phrase.as_bytes(), &salt)
+    }
+
+    /// Generates every distinct seed [`Seed::new_with_options()`][new_with_options] can give for
+    /// the phrase and password, each with the first options that give it
+    ///
+    /// Every normalization form of the phrase is tried with every normalization form of the
+    /// password, as well as the phrase kept as given, and for phrases that aren't plain ASCII also
+    /// words separated by an ideographic space (U+3000). Options that end up hashing the same
+    /// bytes are skipped, so a plain ASCII phrase and password give a single seed. The first seed
+    /// is always the one [`Seed::new()`][Seed::new()] gives for a valid phrase.
+    ///
+    /// Every seed costs a PBKDF2 run, at most fifty-five in all.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bip39::{Seed, SeedOptions};
+    ///
+    /// let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+    ///
+    /// let variants = Seed::variants(phrase, "café");
+    ///
+    /// assert_eq!(variants.len(), 2);
+    /// assert_eq!(variants[0].0, SeedOptions::default());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// With the `self-test` feature, if the [self test][self_test()] fails.
+    ///
+    /// [new_with_options]: ./seed/struct.Seed.html#method.new_with_options
+    /// [Seed::new()]: ./seed/struct.Seed.html#method.new
+    /// [self_test()]: ./fn.self_test.html
+    pub fn variants(phrase: &str, password: &str) -> Vec<(SeedOptions, Seed)> {
+        #[cfg(feature = "self-test")]
+        crate::self_test::assert_on_first_use();
+
+        let separators: &[char] = if phrase.is_ascii() {
+            &[' ']
+        } else {
+            &[' ', IDEOGRAPHIC_SPACE]
+        };
+
+        let mut phrases: Vec<(SeedOptions, SecretBytes)> = Vec::new();
+
+        for &raw_phrase in &[false, true] {
+            for &separator in separators {
+                for &phrase_normalization in Normalization::ALL {
+                    // Neither the separator nor the normalization matter for a phrase kept as given
+                    if raw_phrase
+                        && (separator != ' ' || phrase_normalization != Normalization::None)
+                    {
+                        continue;
+                    }
+
+                    let options = SeedOptions {
+                        phrase_normalization,
+                        separator,
+                        raw_phrase,
+                        ..SeedOptions::default()
+                    };
+                    let input = options.phrase_input(phrase);
+
+                    if phrases
+                        .iter()
+                        .any(|(_, seen)| seen.as_bytes() == input.as_bytes())
+                    {
+                        continue;
+                    }
+
+                    phrases.push((options, input));
+                }
+            }
+        }
+
+        let mut salts: Vec<(Normalization, SecretBytes)> = Vec::new();
+
+        for &password_normalization in Normalization::ALL {
+            let salt = password_normalization.apply("mnemonic", password);
+
+            if salts
+                .iter()
+                .any(|(_, seen)| seen.as_bytes() == salt.as_bytes())
+            {
+                continue;
+            }
+
+            salts.push((password_normalization, salt));
+        }
+
+        let mut variants = Vec::with_capacity(phrases.len() * salts.len());
+
+        for (options, input) in &phrases {
+            for &(password_normalization, ref salt) in &salts {
+                let options = SeedOptions {
+                    password_normalization,
+                    ..*options
+                };
+
+                variants.push((options, Seed::from_inputs(input.as_bytes(), salt)));
+            }
+        }
 
-        Self { bytes }
+        variants
+    }
+
+    /// Generates the seed from the [`Mnemonic`][Mnemonic] and a password held in a type that
+    /// zeroes it on drop, such as `Zeroizing<String>` or, with the `secrecy` feature enabled,
+    /// `secrecy::SecretString`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bip39::{Mnemonic, Language, Seed};
+    /// use zeroize::Zeroizing;
+    ///
+    /// let phrase = "park remain person kitchen mule spell knee armed position rail grid ankle";
+    /// let mnemonic = Mnemonic::from_phrase(phrase, Language::English).unwrap();
+    /// let password = Zeroizing::new(String::from("correct horse battery staple"));
+    ///
+    /// let seed = Seed::new_with_secret(&mnemonic, &password);
+    ///
+    /// assert_eq!(seed, Seed::new(&mnemonic, "correct horse battery staple"));
+    /// ```
+    ///
+    /// [Mnemonic]: ./mnemonic/struct.Mnemonic.html
+    pub fn new_with_secret<P>(mnemonic: &Mnemonic, password: &P) -> Self
+    where
+        P: SecretPassphrase + ?Sized,
+    {
+        Seed::new(mnemonic, password.expose_passphrase())
     }
 
     /// Get the seed value as a byte slice
     pub fn as_bytes(&self) -> &[u8] {
-        &self.bytes
+        self.bytes.as_bytes()
+    }
+
+    /// Whether the seed is held in locked memory
+    #[cfg(feature = "mlock")]
+    pub fn memory_lock(&self) -> MemoryLock {
+        self.bytes.memory_lock()
+    }
+
+    /// Explicitly format the seed, the returned [`Exposed`][Exposed] wrapper implements
+    /// `LowerHex` and `UpperHex`.
+    ///
+    /// [Exposed]: ../exposed/struct.Exposed.html
+    pub fn expose(&self) -> Exposed<'_, Seed> {
+        Exposed(self)
     }
 }
 
-impl AsRef<[u8]> for Seed {
-    fn as_ref(&self) -> &[u8] {
-        self.as_bytes()
+/// A password type that keeps its contents secret, see [`Seed::new_with_secret()`][Seed::new_with_secret()]
+///
+/// [Seed::new_with_secret()]: ./seed/struct.Seed.html#method.new_with_secret
+pub trait SecretPassphrase {
+    /// Borrow the password for the duration of the seed derivation
+    fn expose_passphrase(&self) -> &str;
+}
+
+impl SecretPassphrase for Zeroizing<String> {
+    fn expose_passphrase(&self) -> &str {
+        self
     }
 }
 
-impl fmt::Debug for Seed {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{:#X}", self)
+#[cfg(feature = "secrecy")]
+impl SecretPassphrase for secrecy::SecretString {
+    fn expose_passphrase(&self) -> &str {
+        secrecy::ExposeSecret::expose_secret(self)
     }
 }
 
-impl fmt::LowerHex for Seed {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        if f.alternate() {
-            f.write_str("0x")?;
-        }
+/// Ideographic space, used by some wallets to separate the words of Japanese phrases
+const IDEOGRAPHIC_SPACE: char = '\u{3000}';
 
-        for byte in &self.bytes {
-            write!(f, "{:02x}", byte)?;
-        }
+/// Unicode normalization form used by [`Seed::new_with_options()`][new_with_options]
+///
+/// [new_with_options]: ./seed/struct.Seed.html#method.new_with_options
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Normalization {
+    /// NFKD, as BIP39 specifies
+    Nfkd,
+    /// NFC, composed characters, the form most keyboards and text fields produce
+    Nfc,
+    /// NFD, composed characters split into a base character and combining marks
+    Nfd,
+    /// NFKC, like NFC with compatibility characters such as ligatures and full width letters
+    /// replaced by their plain form
+    Nfkc,
+    /// Use the text as given
+    None,
+}
+
+impl Normalization {
+    const ALL: &'static [Normalization] = &[
+        Normalization::Nfkd,
+        Normalization::Nfc,
+        Normalization::Nfd,
+        Normalization::Nfkc,
+        Normalization::None,
+    ];
 
-        Ok(())
+    /// `prefix` followed by `text` in this form, straight into secret storage
+    fn apply(self, prefix: &str, text: &str) -> SecretBytes {
+        match self {
+            Normalization::Nfkd => secret_from_chars(prefix, || text.nfkd()),
+            Normalization::Nfc => secret_from_chars(prefix, || text.nfc()),
+            Normalization::Nfd => secret_from_chars(prefix, || text.nfd()),
+            Normalization::Nfkc => secret_from_chars(prefix, || text.nfkc()),
+            Normalization::None => secret_from_chars(prefix, || text.chars()),
+        }
     }
 }
 
-impl fmt::UpperHex for Seed {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        if f.alternate() {
-            f.write_str("0x")?;
+/// How [`Seed::new_with_options()`][new_with_options] turns the phrase and password into the
+/// PBKDF2 input and salt
+///
+/// The default is what BIP39 specifies.
+///
+/// [new_with_options]: ./seed/struct.Seed.html#method.new_with_options
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SeedOptions {
+    /// Normalization form of the phrase, unless `raw_phrase` is set
+    pub phrase_normalization: Normalization,
+    /// Normalization form of the password
+    pub password_normalization: Normalization,
+    /// Put between the words of the phrase, before normalizing it. Note that NFKD and NFKC turn
+    /// an ideographic space into a plain space.
+    pub separator: char,
+    /// Use the bytes of the phrase exactly as given, without splitting it into words or
+    /// normalizing it
+    pub raw_phrase: bool,
+}
+
+impl Default for SeedOptions {
+    fn default() -> Self {
+        SeedOptions {
+            phrase_normalization: Normalization::Nfkd,
+            password_normalization: Normalization::Nfkd,
+            separator: ' ',
+            raw_phrase: false,
         }
+    }
+}
 
-        for byte in &self.bytes {
-            write!(f, "{:02X}", byte)?;
+impl SeedOptions {
+    /// The PBKDF2 input and salt
+    fn inputs(&self, phrase: &str, password: &str) -> (SecretBytes, SecretBytes) {
+        (
+            self.phrase_input(phrase),
+            self.password_normalization.apply("mnemonic", password),
+        )
+    }
+
+    /// The PBKDF2 input
+    fn phrase_input(&self, phrase: &str) -> SecretBytes {
+        if self.raw_phrase {
+            return Normalization::None.apply("", phrase);
         }
 
-        Ok(())
+        let mut separator = [0u8; 4];
+        let joined = phrase
+            .split_whitespace()
+            .join(self.separator.encode_utf8(&mut separator));
+
+        self.phrase_normalization.apply("", &joined)
+    }
+}
+
+/// Build the salt, `"mnemonic"` followed by the NFKD normalized password
+fn salt(password: &str) -> SecretBytes {
+    // "mnemonic" is ASCII, so normalizing the parts separately is the same as normalizing
+    // the concatenation.
+    Normalization::Nfkd.apply("mnemonic", password)
+}
+
+/// Write `prefix` and the characters straight into secret storage. The length is measured first,
+/// so the buffer is allocated exactly once and no partial copies are left behind.
+fn secret_from_chars<F, I>(prefix: &str, chars: F) -> SecretBytes
+where
+    F: Fn() -> I,
+    I: Iterator<Item = char>,
+{
+    let len = prefix.len() + chars().map(char::len_utf8).sum::<usize>();
+    let mut secret = SecretBytes::zeroed(len);
+    let buf = secret.as_bytes_mut();
+
+    buf[..prefix.len()].copy_from_slice(prefix.as_bytes());
+
+    let mut offset = prefix.len();
+
+    for ch in chars() {
+        offset += ch.encode_utf8(&mut buf[offset..]).len();
+    }
+
+    secret
+}
+
+impl AsRef<[u8]> for Seed {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+/// Compares the seed bytes in constant time
+impl ConstantTimeEq for Seed {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.as_bytes().ct_eq(other.as_bytes())
+    }
+}
+
+impl PartialEq for Seed {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl Eq for Seed {}
+
+impl fmt::Debug for Seed {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Seed").finish_non_exhaustive()
     }
 }
 
@@ -102,16 +453,70 @@ mod test {
         let mnemonic = Mnemonic::from_entropy(entropy, Language::English).unwrap();
         let seed = Seed::new(&mnemonic, "password");
 
-        assert_eq!(format!("{:x}", seed), "0bde96f14c35a66235478e0c16c152fcaf6301e4d9a81d3febc50879fe7e5438e6a8dd3e39bdf3ab7b12d6b44218710e17d7a2844ee9633fab0e03d9a6c8569b");
-        assert_eq!(format!("{:X}", seed), "0BDE96F14C35A66235478E0C16C152FCAF6301E4D9A81D3FEBC50879FE7E5438E6A8DD3E39BDF3AB7B12D6B44218710E17D7A2844EE9633FAB0E03D9A6C8569B");
-        assert_eq!(format!("{:#x}", seed), "0x0bde96f14c35a66235478e0c16c152fcaf6301e4d9a81d3febc50879fe7e5438e6a8dd3e39bdf3ab7b12d6b44218710e17d7a2844ee9633fab0e03d9a6c8569b");
-        assert_eq!(format!("{:#X}", seed), "0x0BDE96F14C35A66235478E0C16C152FCAF6301E4D9A81D3FEBC50879FE7E5438E6A8DD3E39BDF3AB7B12D6B44218710E17D7A2844EE9633FAB0E03D9A6C8569B");
+        assert_eq!(format!("{:x}", seed.expose()), "0bde96f14c35a66235478e0c16c152fcaf6301e4d9a81d3febc50879fe7e5438e6a8dd3e39bdf3ab7b12d6b44218710e17d7a2844ee9633fab0e03d9a6c8569b");
+        assert_eq!(format!("{:X}", seed.expose()), "0BDE96F14C35A66235478E0C16C152FCAF6301E4D9A81D3FEBC50879FE7E5438E6A8DD3E39BDF3AB7B12D6B44218710E17D7A2844EE9633FAB0E03D9A6C8569B");
+        assert_eq!(format!("{:#x}", seed.expose()), "0x0bde96f14c35a66235478e0c16c152fcaf6301e4d9a81d3febc50879fe7e5438e6a8dd3e39bdf3ab7b12d6b44218710e17d7a2844ee9633fab0e03d9a6c8569b");
+        assert_eq!(format!("{:#X}", seed.expose()), "0x0BDE96F14C35A66235478E0C16C152FCAF6301E4D9A81D3FEBC50879FE7E5438E6A8DD3E39BDF3AB7B12D6B44218710E17D7A2844EE9633FAB0E03D9A6C8569B");
+        assert_eq!(format!("{:?}", seed), "Seed { .. }");
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn seed_eq() {
+        let mnemonic = Mnemonic::from_entropy(&[0x33; 16], Language::English).unwrap();
+
+        let seed = Seed::new(&mnemonic, "password");
+
+        assert_eq!(seed, Seed::new(&mnemonic, "password"));
+        assert_ne!(seed, Seed::new(&mnemonic, "Password"));
+    }
+
+    #[test]
+    #[cfg(all(feature = "mlock", unix))]
+    fn seed_memory_lock() {
+        let mnemonic = Mnemonic::from_entropy(&[0x33; 16], Language::English).unwrap();
+        let seed = Seed::new(&mnemonic, "");
+
+        assert_ne!(seed.memory_lock(), MemoryLock::Unavailable);
+        assert_eq!(seed.memory_lock(), seed.clone().memory_lock());
+        assert_eq!(mnemonic.memory_lock(), seed.memory_lock());
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn seed_with_secret() {
+        let mnemonic = Mnemonic::from_entropy(&[0x33; 16], Language::English).unwrap();
+        let password = Zeroizing::new(String::from("nullius　à　nym.zone ¹teſts²"));
+
+        assert_eq!(
+            Seed::new_with_secret(&mnemonic, &password),
+            Seed::new(&mnemonic, &password)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "secrecy")]
+    fn seed_with_secrecy() {
+        let mnemonic = Mnemonic::from_entropy(&[0x33; 16], Language::English).unwrap();
+        let password = secrecy::SecretString::from("password");
+
+        assert_eq!(
+            Seed::new_with_secret(&mnemonic, &password),
+            Seed::new(&mnemonic, "password")
+        );
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn salt_is_normalized() {
+        assert_eq!(salt("").as_bytes(), b"mnemonic");
+        assert_eq!(salt("ﬁ é").as_bytes(), "mnemonicfi e\u{301}".as_bytes());
     }
 
     fn test_unicode_normalization(lang: Language, phrase: &str, password: &str, expected_seed_hex: &str) {
         let mnemonic = Mnemonic::from_phrase(phrase, lang).unwrap();
         let seed = Seed::new(&mnemonic, password);
-        assert_eq!(format!("{:x}", seed), expected_seed_hex);
+        assert_eq!(format!("{:x}", seed.expose()), expected_seed_hex);
     }
 
     #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
@@ -175,4 +580,184 @@ mod test {
             "cff9ffd2b23549e73601db4129a334c81b28a40f0ee819b5d6a54c409999f0dfb6b89df17cae6408c96786165c205403d283baadc03ffdd391a490923b7d9493",
         );
     }
+
+    fn test_entropy_vector(lang: Language, entropy_hex: &str, phrase: &str, expected_seed_hex: &str) {
+        let entropy = hex::decode(entropy_hex).unwrap();
+        let mnemonic = Mnemonic::from_entropy(&entropy, lang).unwrap();
+        assert_eq!(mnemonic.phrase(), phrase);
+        test_unicode_normalization(lang, phrase, "TREZOR", expected_seed_hex);
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    /// There are no upstream vectors for Czech, these were computed with Python's hashlib against
+    /// the official czech.txt
+    #[cfg(feature = "czech")]
+    fn czech_vectors() {
+        test_entropy_vector(
+            Language::Czech,
+            "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+            "obrazec znak uznat zubovina zeman skupina zrcadlo vzchopit obrazec znak uznat zubr",
+            "68e1bd31ed5f20c9ab108c03b524e85209b0b27af80cb5d48fa71d03dbb528b73c2349bb8576f9b68825272984061594f520e54605a4898ba61c433d06bf5de7",
+        );
+        test_entropy_vector(
+            Language::Czech,
+            "8080808080808080808080808080808080808080808080808080808080808080",
+            "obvinit bageta doma amputace bidlo jedle arogance butik obvinit bageta doma amputace bidlo jedle arogance butik obvinit bageta doma amputace bidlo jedle arogance cihla",
+            "bb8b82baced0db7764e102d1d1f68035269e84ec6c1ed0e09b2a31094330967aff9e1a490a407fef736fb8719c60bfb8cb0be9b27fce97c3b619409c195e2f1d",
+        );
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    /// There are no upstream vectors for Portuguese, these were computed with Python's hashlib
+    /// against the official portuguese.txt
+    #[cfg(feature = "portuguese")]
+    fn portuguese_vectors() {
+        test_entropy_vector(
+            Language::Portuguese,
+            "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+            "imitador vinheta sogro xerife veleiro pomar volumoso tratador imitador vinheta sogro xingar",
+            "298d1614ff06ae803709f5be5331135cb74e6cc77fa09e07a3e887c2e370401f9a73a409dadf58b5a5197b27ffb3fa5dd528aad9a1a8750d7669ce950ee60c2c",
+        );
+        test_entropy_vector(
+            Language::Portuguese,
+            "8080808080808080808080808080808080808080808080808080808080808080",
+            "inalador acirrar barulho abotoar afivelar coruja abutre amostra inalador acirrar barulho abotoar afivelar coruja abutre amostra inalador acirrar barulho abotoar afivelar coruja abutre asilado",
+            "32c8feae6a0bee33166468a770cb28459727e10f4f5ffef64977d5ef52a68ec51d832751a10c025058612ab256052cdfa9d8c5c87560de0453efe5a7d4597771",
+        );
+    }
+
+    const ABANDON_ABOUT: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn default_options_are_bip39() {
+        let mnemonic = Mnemonic::from_phrase(ABANDON_ABOUT, Language::English).unwrap();
+        let password = "nullius　à　nym.zone ¹teſts² English";
+
+        assert_eq!(
+            Seed::new_with_options(ABANDON_ABOUT, password, SeedOptions::default()),
+            Seed::new(&mnemonic, password)
+        );
+        // Extra whitespace is dropped like `Mnemonic::from_phrase()` does
+        assert_eq!(
+            Seed::new_with_options(" abandon  abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about\n", "", SeedOptions::default()),
+            Seed::new(&mnemonic, "")
+        );
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    /// Expected seeds computed with Python's hashlib and unicodedata
+    fn nfc_password() {
+        let options = SeedOptions {
+            password_normalization: Normalization::Nfc,
+            ..SeedOptions::default()
+        };
+        let seed = Seed::new_with_options(ABANDON_ABOUT, "caf\u{e9}", options);
+
+        assert_eq!(
+            format!("{:x}", seed.expose()),
+            "3f71749629d237c8505c83d334e9a05d9430650ee41a5da768cb4bfe1ebd0882d4ee4fe800aed9a22d8769a9bd404eedec49dcdb80b4e07e624312de775e995e"
+        );
+
+        let seed = Seed::new_with_options(ABANDON_ABOUT, "caf\u{e9}", SeedOptions::default());
+
+        assert_eq!(
+            format!("{:x}", seed.expose()),
+            "af8bbd2566df7b69d926f2b09dfdbd75db6c994a3399b2cc65f928d63e3fd4e61218ee0d15f8c810be4d45e66d47b43c15a5cc753976b1666912377ff7ae9818"
+        );
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn invalid_checksum_is_accepted() {
+        let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon";
+
+        assert!(Mnemonic::from_phrase(phrase, Language::English).is_err());
+        assert_ne!(
+            Seed::new_with_options(phrase, "", SeedOptions::default()),
+            Seed::new_with_options(ABANDON_ABOUT, "", SeedOptions::default())
+        );
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    /// Expected seed computed with Python's hashlib, from the unnormalized phrase and password
+    fn raw_japanese_phrase() {
+        let phrase = "あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あおぞら";
+        let password = "㍍ガバヴァぱばぐゞちぢ十人十色";
+        let raw = SeedOptions {
+            phrase_normalization: Normalization::None,
+            password_normalization: Normalization::None,
+            separator: ' ',
+            raw_phrase: true,
+        };
+        let ideographic = SeedOptions {
+            phrase_normalization: Normalization::None,
+            password_normalization: Normalization::None,
+            separator: IDEOGRAPHIC_SPACE,
+            raw_phrase: false,
+        };
+        let expected = "d35d80999a9ad4c4b305a003080487940d04a5f0e727d686dd446df6d8a04acf7bce5ee151c8bd438c9cbd436d0824555e430855151ec22090c4f42bc7fbc098";
+
+        assert_eq!(
+            format!("{:x}", Seed::new_with_options(phrase, password, raw).expose()),
+            expected
+        );
+        assert_eq!(
+            format!("{:x}", Seed::new_with_options(phrase, password, ideographic).expose()),
+            expected
+        );
+        assert_eq!(
+            format!("{:x}", Seed::new_with_options(phrase, password, SeedOptions::default()).expose()),
+            "a262d6fb6122ecf45be09c50492b31f92e9beb7d9a845987a02cefda57a15f9c467a17872029a9e92299b5cbdf306e3a0ee620245cbd508959b6cb7ca637bd55"
+        );
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn seed_variants() {
+        let mnemonic = Mnemonic::from_phrase(ABANDON_ABOUT, Language::English).unwrap();
+
+        let variants = Seed::variants(ABANDON_ABOUT, "TREZOR");
+
+        assert_eq!(variants.len(), 1);
+        assert_eq!(variants[0].1, Seed::new(&mnemonic, "TREZOR"));
+
+        let variants = Seed::variants(ABANDON_ABOUT, "caf\u{e9}");
+
+        assert_eq!(variants.len(), 2);
+        assert_eq!(variants[0].0, SeedOptions::default());
+        assert_eq!(variants[1].0.phrase_normalization, Normalization::Nfkd);
+        assert_eq!(variants[1].0.password_normalization, Normalization::Nfc);
+        assert_ne!(variants[0].1, variants[1].1);
+
+        let phrase = "あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あおぞら";
+        let variants = Seed::variants(phrase, "㍍ガバヴァぱばぐゞちぢ十人十色");
+        let raw = Seed::new_with_options(
+            phrase,
+            "㍍ガバヴァぱばぐゞちぢ十人十色",
+            SeedOptions {
+                phrase_normalization: Normalization::None,
+                password_normalization: Normalization::None,
+                separator: ' ',
+                raw_phrase: true,
+            },
+        );
+
+        assert!(variants.len() > 2);
+        assert_eq!(variants[0].0, SeedOptions::default());
+        assert!(variants.iter().any(|(_, seed)| *seed == raw));
+        // Phrase and password forms are combined freely
+        assert!(variants.iter().any(|(options, _)| {
+            options.phrase_normalization == Normalization::Nfc
+                && options.password_normalization == Normalization::Nfkd
+        }));
+        // Every variant is distinct
+        for (idx, (_, seed)) in variants.iter().enumerate() {
+            assert!(variants[idx + 1..].iter().all(|(_, other)| other != seed));
+        }
+    }
 }
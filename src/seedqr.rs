@@ -0,0 +1,324 @@
+//! [SeedQR][seedqr] encoding, as used by SeedSigner and compatible devices.
+//!
+//! A standard SeedQR is the concatenation of each word's index in the word list, zero-padded to
+//! four digits and encoded as a numeric QR code. A CompactSeedQR is the raw entropy encoded as a
+//! byte mode QR code. Both use error correction level L and the smallest QR version that fits.
+//!
+//! The SeedQR specification is defined for English, but the word indices are the same for every
+//! language, so the parsers take a [`Language`][Language] like
+//! [`Mnemonic::from_entropy()`][Mnemonic::from_entropy()] does.
+//!
+//! [seedqr]: https://github.com/SeedSigner/seedsigner/blob/dev/docs/seed_qr/README.md
+//! [Language]: ../language/enum.Language.html
+//! [Mnemonic::from_entropy()]: ../mnemonic/struct.Mnemonic.html#method.from_entropy
+
+use crate::error::ErrorKind;
+use crate::language::Language;
+use crate::mnemonic::Mnemonic;
+use crate::util::{Bits11, IterExt};
+use anyhow::Error;
+#[cfg(feature = "qr")]
+use std::fmt;
+#[cfg(feature = "qr")]
+use zeroize::Zeroize;
+use zeroize::Zeroizing;
+
+/// Number of digits encoding a single word in a SeedQR
+const DIGITS_PER_WORD: usize = 4;
+
+impl Mnemonic {
+    /// Get the SeedQR digit string for this mnemonic
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bip39::{Mnemonic, Language};
+    ///
+    /// let phrase = "forum undo fragile fade shy sign arrest garment culture tube off merit";
+    /// let mnemonic = Mnemonic::from_phrase(phrase, Language::English).unwrap();
+    ///
+    /// assert_eq!(
+    ///     &*mnemonic.to_seedqr_digits(),
+    ///     "073318950739065415961602009907670428187212261116"
+    /// );
+    /// ```
+    pub fn to_seedqr_digits(&self) -> Zeroizing<String> {
+        let wordmap = self.language().wordmap();
+        let mut digits = Zeroizing::new(String::with_capacity(
+            self.phrase().split(' ').count() * DIGITS_PER_WORD,
+        ));
+
+        for word in self.phrase().split(' ') {
+            let index = u16::from(wordmap.get_bits(word).expect("phrase is valid"));
+
+            for divisor in &[1000, 100, 10, 1] {
+                digits.push((b'0' + (index / divisor % 10) as u8) as char);
+            }
+        }
+
+        digits
+    }
+
+    /// Create a [`Mnemonic`][Mnemonic] from a SeedQR digit string
+    ///
+    /// The digit count must be a multiple of four, otherwise an `Error` of kind
+    /// `ErrorKind::InvalidSeedQr` is returned. The resulting phrase is validated just like
+    /// [`Mnemonic::from_phrase()`][Mnemonic::from_phrase()].
+    ///
+    /// [Mnemonic]: ./mnemonic/struct.Mnemonic.html
+    /// [Mnemonic::from_phrase()]: ./mnemonic/struct.Mnemonic.html#method.from_phrase
+    pub fn from_seedqr_digits(digits: &str, lang: Language) -> Result<Mnemonic, Error> {
+        let digits = digits.as_bytes();
+
+        if digits.len() % DIGITS_PER_WORD != 0 || !digits.iter().all(u8::is_ascii_digit) {
+            Err(ErrorKind::InvalidSeedQr)?;
+        }
+
+        let to_index = |chunk: &[u8]| {
+            chunk
+                .iter()
+                .fold(0u16, |index, digit| index * 10 + (digit - b'0') as u16)
+        };
+
+        for (idx, chunk) in digits.chunks(DIGITS_PER_WORD).enumerate() {
+            if to_index(chunk) >= 2048 {
+                Err(ErrorKind::InvalidWord(idx))?;
+            }
+        }
+
+        let wordlist = lang.try_wordlist()?;
+        let phrase = digits
+            .chunks(DIGITS_PER_WORD)
+            .map(|chunk| wordlist.get_word(Bits11::from(to_index(chunk))))
+            .join(" ");
+
+        Mnemonic::from_phrase(&phrase, lang)
+    }
+
+    /// Get the CompactSeedQR payload for this mnemonic, which is its entropy
+    pub fn to_compact_seedqr(&self) -> Zeroizing<Vec<u8>> {
+        Zeroizing::new(self.entropy().to_vec())
+    }
+
+    /// Create a [`Mnemonic`][Mnemonic] from a CompactSeedQR payload
+    ///
+    /// [Mnemonic]: ./mnemonic/struct.Mnemonic.html
+    pub fn from_compact_seedqr(bytes: &[u8], lang: Language) -> Result<Mnemonic, Error> {
+        Mnemonic::from_entropy(bytes, lang)
+    }
+
+    /// Generate the SeedQR code for this mnemonic
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bip39::{Mnemonic, MnemonicType, Language};
+    ///
+    /// let mnemonic = Mnemonic::new(MnemonicType::Words24, Language::English);
+    /// let qr = mnemonic.to_seedqr_matrix();
+    ///
+    /// assert_eq!(qr.width(), 29);
+    ///
+    /// for y in 0..qr.width() {
+    ///     let row: String = (0..qr.width())
+    ///         .map(|x| if qr.is_dark(x, y) { '#' } else { ' ' })
+    ///         .collect();
+    ///
+    ///     // draw the row
+    /// }
+    /// ```
+    #[cfg(feature = "qr")]
+    pub fn to_seedqr_matrix(&self) -> QrMatrix {
+        QrMatrix::encode(qrcodegen::QrSegment::make_numeric(&self.to_seedqr_digits()))
+    }
+
+    /// Generate the CompactSeedQR code for this mnemonic
+    #[cfg(feature = "qr")]
+    pub fn to_compact_seedqr_matrix(&self) -> QrMatrix {
+        QrMatrix::encode(qrcodegen::QrSegment::make_bytes(self.entropy()))
+    }
+}
+
+/// Modules of a QR code, see [`Mnemonic::to_seedqr_matrix()`][Mnemonic::to_seedqr_matrix()].
+///
+/// The matrix doesn't include the quiet zone, renderers should leave a light border of at least
+/// four modules around it. The modules are zeroed on drop, but intermediate buffers of the QR
+/// encoder are not.
+///
+/// [Mnemonic::to_seedqr_matrix()]: ./mnemonic/struct.Mnemonic.html#method.to_seedqr_matrix
+#[cfg(feature = "qr")]
+#[derive(Clone, Zeroize)]
+#[zeroize(drop)]
+pub struct QrMatrix {
+    width: usize,
+    modules: Vec<bool>,
+}
+
+#[cfg(feature = "qr")]
+impl QrMatrix {
+    fn encode(segment: qrcodegen::QrSegment) -> QrMatrix {
+        let code = qrcodegen::QrCode::encode_segments_advanced(
+            &[segment],
+            qrcodegen::QrCodeEcc::Low,
+            qrcodegen::Version::MIN,
+            qrcodegen::Version::MAX,
+            None,
+            false,
+        )
+        .expect("mnemonics always fit in a QR code");
+
+        let width = code.size() as usize;
+        let modules = (0..width * width)
+            .map(|idx| code.get_module((idx % width) as i32, (idx / width) as i32))
+            .collect();
+
+        QrMatrix { width, modules }
+    }
+
+    /// Number of modules on each side
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Whether the module at column `x` and row `y` is dark, `(0, 0)` being the top left corner
+    pub fn is_dark(&self, x: usize, y: usize) -> bool {
+        assert!(x < self.width && y < self.width, "module out of bounds");
+
+        self.modules[y * self.width + x]
+    }
+
+    /// All modules, row by row, `true` being dark
+    pub fn modules(&self) -> &[bool] {
+        &self.modules
+    }
+}
+
+#[cfg(feature = "qr")]
+impl fmt::Debug for QrMatrix {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("QrMatrix")
+            .field("width", &self.width)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    // Examples from the SeedQR specification
+    const PHRASE_12: &str =
+        "forum undo fragile fade shy sign arrest garment culture tube off merit";
+    const DIGITS_12: &str = "073318950739065415961602009907670428187212261116";
+    const ENTROPY_12: &str = "5bbd9d71a8ec7990831aff359d426545";
+    const PHRASE_24: &str = "attack pizza motion avocado network gather crop fresh patrol unusual wild holiday candy pony ranch winter theme error hybrid van cereal salon goddess expire";
+    const DIGITS_24: &str = "011513251154012711900771041507421289190620080870026613431420201617920614089619290300152408010643";
+    const ENTROPY_24: &str = "0e74b64107f94cc0ccfae6a13dcbec3662154fec67e0e00999c07892597d190a";
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn seedqr_digits() {
+        for &(phrase, digits) in &[(PHRASE_12, DIGITS_12), (PHRASE_24, DIGITS_24)] {
+            let mnemonic = Mnemonic::from_phrase(phrase, Language::English).unwrap();
+
+            assert_eq!(&*mnemonic.to_seedqr_digits(), digits);
+            assert_eq!(
+                Mnemonic::from_seedqr_digits(digits, Language::English).unwrap(),
+                mnemonic
+            );
+        }
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn compact_seedqr() {
+        for &(phrase, entropy) in &[(PHRASE_12, ENTROPY_12), (PHRASE_24, ENTROPY_24)] {
+            let mnemonic = Mnemonic::from_phrase(phrase, Language::English).unwrap();
+            let entropy = hex::decode(entropy).unwrap();
+
+            assert_eq!(&*mnemonic.to_compact_seedqr(), &entropy);
+            assert_eq!(
+                Mnemonic::from_compact_seedqr(&entropy, Language::English).unwrap(),
+                mnemonic
+            );
+        }
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn invalid_seedqr_digits() {
+        let lang = Language::English;
+
+        assert!(Mnemonic::from_seedqr_digits(&DIGITS_12[1..], lang).is_err());
+        assert!(Mnemonic::from_seedqr_digits(&DIGITS_12.replace('0', "a"), lang).is_err());
+        assert!(
+            Mnemonic::from_seedqr_digits(&DIGITS_12.replacen("0733", "2048", 1), lang).is_err()
+        );
+        assert!(
+            Mnemonic::from_seedqr_digits(&DIGITS_12.replacen("0733", "0734", 1), lang).is_err()
+        );
+        assert!(Mnemonic::from_seedqr_digits(&DIGITS_12[4..], lang).is_err());
+        assert!(Mnemonic::from_seedqr_digits("", lang).is_err());
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg(feature = "qr")]
+    fn seedqr_versions() {
+        let words_12 = Mnemonic::from_phrase(PHRASE_12, Language::English).unwrap();
+        let words_24 = Mnemonic::from_phrase(PHRASE_24, Language::English).unwrap();
+
+        // Versions 2 and 3 for SeedQR, 1 and 2 for CompactSeedQR, as listed in the specification
+        assert_eq!(words_12.to_seedqr_matrix().width(), 25);
+        assert_eq!(words_24.to_seedqr_matrix().width(), 29);
+        assert_eq!(words_12.to_compact_seedqr_matrix().width(), 21);
+        assert_eq!(words_24.to_compact_seedqr_matrix().width(), 25);
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg(feature = "qr")]
+    fn compact_seedqr_matrix() {
+        let mnemonic = Mnemonic::from_phrase(PHRASE_12, Language::English).unwrap();
+        let qr = mnemonic.to_compact_seedqr_matrix();
+
+        let rows: Vec<String> = (0..qr.width())
+            .map(|y| {
+                (0..qr.width())
+                    .map(|x| if qr.is_dark(x, y) { '#' } else { '.' })
+                    .collect()
+            })
+            .collect();
+
+        // Cross-checked against the `qrcode` crate with the same mask (2)
+        assert_eq!(
+            rows,
+            [
+                "#######.....#.#######",
+                "#.....#.#..##.#.....#",
+                "#.###.#..#.##.#.###.#",
+                "#.###.#.##.##.#.###.#",
+                "#.###.#...#...#.###.#",
+                "#.....#.#####.#.....#",
+                "#######.#.#.#.#######",
+                "..........#..........",
+                "#####.####..##.#.#.#.",
+                "##.###.##...#....#.##",
+                "#..#####.####..####.#",
+                "..###..#.####.#....##",
+                "..###.#.##.#...####.#",
+                "........#####...##.#.",
+                "#######.##...##..###.",
+                "#.....#...###..#.##..",
+                "#.###.#.#..###..##...",
+                "#.###.#.#..#..###.##.",
+                "#.###.#.##..#.#.###..",
+                "#.....#.#.##.###.....",
+                "#######.#.##.####..#.",
+            ]
+        );
+        assert_eq!(format!("{:?}", qr), "QrMatrix { width: 21, .. }");
+    }
+}
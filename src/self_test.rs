@@ -0,0 +1,148 @@
+//! Known answer tests for the primitives every [`Mnemonic`][Mnemonic] and [`Seed`][Seed] is
+//! built from.
+//!
+//! The vectors are taken from the Trezor reference vectors in `tests/standard-vectors.rs`. With
+//! the `self-test` feature the tests also run once, before the first [`Mnemonic`][Mnemonic] or
+//! [`Seed`][Seed] is created, so nothing is ever derived from a broken primitive. Constructors
+//! that return a `Result` return the failure as an `Error` of kind `ErrorKind::SelfTestFailed`,
+//! the others panic. Call [`self_test()`][self_test()] at startup to handle a failure before
+//! any of them run.
+//!
+//! [Mnemonic]: ../mnemonic/struct.Mnemonic.html
+//! [Seed]: ../seed/struct.Seed.html
+//! [self_test()]: ../fn.self_test.html
+
+use crate::crypto::{pbkdf2, sha256_first_byte, PBKDF2_BYTES};
+use crate::error::ErrorKind;
+use crate::language::Language;
+use crate::mnemonic::Mnemonic;
+use crate::seed::Seed;
+use anyhow::Error;
+#[cfg(feature = "self-test")]
+use std::sync::atomic::{AtomicBool, Ordering};
+
+const PASSWORD: &str = "TREZOR";
+
+const ENTROPY_ZERO: [u8; 16] = [0x00; 16];
+const PHRASE_ZERO: &str =
+    "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+const SEED_ZERO: [u8; PBKDF2_BYTES] = [
+    0xc5, 0x52, 0x57, 0xc3, 0x60, 0xc0, 0x7c, 0x72, 0x02, 0x9a, 0xeb, 0xc1, 0xb5, 0x3c, 0x05, 0xed,
+    0x03, 0x62, 0xad, 0xa3, 0x8e, 0xad, 0x3e, 0x3e, 0x9e, 0xfa, 0x37, 0x08, 0xe5, 0x34, 0x95, 0x53,
+    0x1f, 0x09, 0xa6, 0x98, 0x75, 0x99, 0xd1, 0x82, 0x64, 0xc1, 0xe1, 0xc9, 0x2f, 0x2c, 0xf1, 0x41,
+    0x63, 0x0c, 0x7a, 0x3c, 0x4a, 0xb7, 0xc8, 0x1b, 0x2f, 0x00, 0x16, 0x98, 0xe7, 0x46, 0x3b, 0x04,
+];
+
+const ENTROPY_7F: [u8; 16] = [0x7f; 16];
+const PHRASE_7F: &str =
+    "legal winner thank year wave sausage worth useful legal winner thank yellow";
+const SEED_7F: [u8; PBKDF2_BYTES] = [
+    0x2e, 0x89, 0x05, 0x81, 0x9b, 0x87, 0x23, 0xfe, 0x2c, 0x1d, 0x16, 0x18, 0x60, 0xe5, 0xee, 0x18,
+    0x30, 0x31, 0x8d, 0xbf, 0x49, 0xa8, 0x3b, 0xd4, 0x51, 0xcf, 0xb8, 0x44, 0x0c, 0x28, 0xbd, 0x6f,
+    0xa4, 0x57, 0xfe, 0x12, 0x96, 0x10, 0x65, 0x59, 0xa3, 0xc8, 0x09, 0x37, 0xa1, 0xc1, 0x06, 0x9b,
+    0xe3, 0xa3, 0xa5, 0xbd, 0x38, 0x1e, 0xe6, 0x26, 0x0e, 0x8d, 0x97, 0x39, 0xfc, 0xe1, 0xf6, 0x07,
+];
+
+/// Set once the tests have passed, so they run only once per process
+#[cfg(feature = "self-test")]
+static PASSED: AtomicBool = AtomicBool::new(false);
+
+/// Run the known answer tests for SHA-256, PBKDF2, entropy to phrase and phrase to seed
+///
+/// Returns an `Error` of kind `ErrorKind::SelfTestFailed` naming the first test that failed.
+/// Each run derives two seeds, which takes about as long as two calls to
+/// [`Seed::new()`][Seed::new()].
+///
+/// # Example
+///
+/// ```
+/// bip39::self_test().expect("crypto primitives are broken");
+/// ```
+///
+/// [Seed::new()]: ./seed/struct.Seed.html#method.new
+pub fn self_test() -> Result<(), Error> {
+    // The checksum byte of each entropy vector
+    if sha256_first_byte(&ENTROPY_ZERO) != 0x37 || sha256_first_byte(&ENTROPY_7F) != 0x87 {
+        Err(ErrorKind::SelfTestFailed("SHA-256"))?;
+    }
+
+    let mut seed = [0u8; PBKDF2_BYTES];
+
+    pbkdf2(PHRASE_ZERO.as_bytes(), b"mnemonicTREZOR", &mut seed);
+
+    if seed != SEED_ZERO {
+        Err(ErrorKind::SelfTestFailed("PBKDF2"))?;
+    }
+
+    if *Mnemonic::entropy_to_phrase(&ENTROPY_ZERO, Language::English)? != PHRASE_ZERO
+        || *Mnemonic::entropy_to_phrase(&ENTROPY_7F, Language::English)? != PHRASE_7F
+    {
+        Err(ErrorKind::SelfTestFailed("entropy to phrase"))?;
+    }
+
+    if Seed::derive(PHRASE_7F, PASSWORD).as_bytes() != SEED_7F {
+        Err(ErrorKind::SelfTestFailed("phrase to seed"))?;
+    }
+
+    #[cfg(feature = "self-test")]
+    PASSED.store(true, Ordering::Release);
+
+    Ok(())
+}
+
+/// Run the tests before the first `Mnemonic` or `Seed` is created
+#[cfg(feature = "self-test")]
+pub(crate) fn run_on_first_use() -> Result<(), Error> {
+    if PASSED.load(Ordering::Acquire) {
+        return Ok(());
+    }
+
+    self_test()
+}
+
+/// Run the tests before the first `Seed` is created, for constructors that can't return an error
+#[cfg(feature = "self-test")]
+pub(crate) fn assert_on_first_use() {
+    if let Err(err) = run_on_first_use() {
+        panic!("{}", err);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn self_test_passes() {
+        self_test().unwrap();
+
+        #[cfg(feature = "self-test")]
+        assert!(PASSED.load(Ordering::Acquire));
+    }
+
+    #[cfg(feature = "self-test")]
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn first_use_returns_result() {
+        run_on_first_use().unwrap();
+        assert!(PASSED.load(Ordering::Acquire));
+        run_on_first_use().unwrap();
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn vectors_match_public_api() {
+        let mnemonic = Mnemonic::from_entropy(&ENTROPY_7F, Language::English).unwrap();
+
+        assert_eq!(mnemonic.phrase(), PHRASE_7F);
+        assert_eq!(Seed::new(&mnemonic, PASSWORD).as_bytes(), &SEED_7F[..]);
+
+        let mnemonic = Mnemonic::from_phrase(PHRASE_ZERO, Language::English).unwrap();
+
+        assert_eq!(mnemonic.entropy(), &ENTROPY_ZERO[..]);
+        assert_eq!(Seed::new(&mnemonic, PASSWORD).as_bytes(), &SEED_ZERO[..]);
+    }
+}
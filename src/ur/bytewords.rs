@@ -0,0 +1,248 @@
+//! [Bytewords][bytewords] encoding, which maps every byte to a four letter word.
+//!
+//! Encoded data always ends with the CRC32 checksum of the payload, which is checked and stripped
+//! when decoding. Uniform Resources use the [`Minimal`][Minimal] style, which keeps only the first
+//! and last letter of each word.
+//!
+//! ```
+//! use bip39::ur::bytewords::{self, Style};
+//!
+//! let data = [0, 1, 2, 128, 255];
+//!
+//! assert_eq!(&*bytewords::encode(&data, Style::Minimal), "aeadaolazmjendeoti");
+//! assert_eq!(&**bytewords::decode("aeadaolazmjendeoti", Style::Minimal).unwrap(), &data);
+//! ```
+//!
+//! [bytewords]: https://github.com/BlockchainCommons/Research/blob/master/papers/bcr-2020-012-bytewords.md
+//! [Minimal]: ./enum.Style.html#variant.Minimal
+
+use crate::error::ErrorKind;
+use anyhow::Error;
+use once_cell::sync::Lazy;
+use zeroize::Zeroizing;
+
+/// How the words are written out
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Style {
+    /// Full words separated by spaces, `able acid also`
+    Standard,
+    /// Full words separated by dashes, `able-acid-also`
+    Uri,
+    /// First and last letter of each word with no separator, `aeadao`
+    Minimal,
+}
+
+const WORDS: [&str; 256] = [
+    "able", "acid", "also", "apex", "aqua", "arch", "atom", "aunt", "away", "axis", "back", "bald",
+    "barn", "belt", "beta", "bias", "blue", "body", "brag", "brew", "bulb", "buzz", "calm", "cash",
+    "cats", "chef", "city", "claw", "code", "cola", "cook", "cost", "crux", "curl", "cusp", "cyan",
+    "dark", "data", "days", "deli", "dice", "diet", "door", "down", "draw", "drop", "drum", "dull",
+    "duty", "each", "easy", "echo", "edge", "epic", "even", "exam", "exit", "eyes", "fact", "fair",
+    "fern", "figs", "film", "fish", "fizz", "flap", "flew", "flux", "foxy", "free", "frog", "fuel",
+    "fund", "gala", "game", "gear", "gems", "gift", "girl", "glow", "good", "gray", "grim", "guru",
+    "gush", "gyro", "half", "hang", "hard", "hawk", "heat", "help", "high", "hill", "holy", "hope",
+    "horn", "huts", "iced", "idea", "idle", "inch", "inky", "into", "iris", "iron", "item", "jade",
+    "jazz", "join", "jolt", "jowl", "judo", "jugs", "jump", "junk", "jury", "keep", "keno", "kept",
+    "keys", "kick", "kiln", "king", "kite", "kiwi", "knob", "lamb", "lava", "lazy", "leaf", "legs",
+    "liar", "limp", "lion", "list", "logo", "loud", "love", "luau", "luck", "lung", "main", "many",
+    "math", "maze", "memo", "menu", "meow", "mild", "mint", "miss", "monk", "nail", "navy", "need",
+    "news", "next", "noon", "note", "numb", "obey", "oboe", "omit", "onyx", "open", "oval", "owls",
+    "paid", "part", "peck", "play", "plus", "poem", "pool", "pose", "puff", "puma", "purr", "quad",
+    "quiz", "race", "ramp", "real", "redo", "rich", "road", "rock", "roof", "ruby", "ruin", "runs",
+    "rust", "safe", "saga", "scar", "sets", "silk", "skew", "slot", "soap", "solo", "song", "stub",
+    "surf", "swan", "taco", "task", "taxi", "tent", "tied", "time", "tiny", "toil", "tomb", "toys",
+    "trip", "tuna", "twin", "ugly", "undo", "unit", "urge", "user", "vast", "very", "veto", "vial",
+    "vibe", "view", "visa", "void", "vows", "wall", "wand", "warm", "wasp", "wave", "waxy", "webs",
+    "what", "when", "whiz", "wolf", "work", "yank", "yawn", "yell", "yoga", "yurt", "zaps", "zero",
+    "zest", "zinc", "zone", "zoom",
+];
+
+/// Byte for each pair of first and last letters, the pairs are unique across the list
+static MINIMAL: Lazy<[Option<u8>; 26 * 26]> = Lazy::new(|| {
+    let mut table = [None; 26 * 26];
+
+    for (byte, word) in WORDS.iter().enumerate() {
+        let word = word.as_bytes();
+
+        table[pair_index(word[0], word[3]).expect("words are lowercase")] = Some(byte as u8);
+    }
+
+    table
+});
+
+fn pair_index(first: u8, last: u8) -> Option<usize> {
+    let first = first
+        .to_ascii_lowercase()
+        .checked_sub(b'a')
+        .filter(|&c| c < 26)?;
+    let last = last
+        .to_ascii_lowercase()
+        .checked_sub(b'a')
+        .filter(|&c| c < 26)?;
+
+    Some(first as usize * 26 + last as usize)
+}
+
+/// CRC32 checksum used by bytewords and the fountain encoder
+pub(crate) fn crc32(data: &[u8]) -> u32 {
+    crc32fast::hash(data)
+}
+
+/// Encode `data` followed by its CRC32 checksum
+pub fn encode(data: &[u8], style: Style) -> Zeroizing<String> {
+    let checksum = crc32(data).to_be_bytes();
+    let bytes = data.iter().chain(checksum.iter());
+    let count = data.len() + checksum.len();
+
+    let mut out = Zeroizing::new(String::with_capacity(match style {
+        Style::Minimal => count * 2,
+        _ => count * 5,
+    }));
+
+    for (idx, &byte) in bytes.enumerate() {
+        let word = WORDS[byte as usize];
+
+        match style {
+            Style::Minimal => {
+                out.push_str(&word[..1]);
+                out.push_str(&word[3..]);
+                continue;
+            }
+            Style::Standard if idx > 0 => out.push(' '),
+            Style::Uri if idx > 0 => out.push('-'),
+            _ => (),
+        }
+
+        out.push_str(word);
+    }
+
+    out
+}
+
+/// Decode bytewords, verifying and removing the trailing CRC32 checksum
+///
+/// Words are matched case insensitively. Malformed input or a checksum mismatch returns an
+/// `Error` of kind `ErrorKind::InvalidUr`.
+pub fn decode(encoded: &str, style: Style) -> Result<Zeroizing<Vec<u8>>, Error> {
+    let mut bytes = Zeroizing::new(Vec::with_capacity(encoded.len() / 2));
+
+    match style {
+        Style::Minimal => {
+            if encoded.len() % 2 != 0 {
+                Err(ErrorKind::InvalidUr("invalid bytewords length"))?;
+            }
+
+            for pair in encoded.as_bytes().chunks(2) {
+                bytes.push(decode_pair(pair[0], pair[1])?);
+            }
+        }
+        Style::Standard | Style::Uri => {
+            let separator = if style == Style::Standard { ' ' } else { '-' };
+
+            for word in encoded.split(separator) {
+                let word = word.as_bytes();
+
+                if word.len() != 4 {
+                    Err(ErrorKind::InvalidUr("invalid byteword"))?;
+                }
+
+                let byte = decode_pair(word[0], word[3])?;
+
+                if !WORDS[byte as usize].as_bytes().eq_ignore_ascii_case(word) {
+                    Err(ErrorKind::InvalidUr("invalid byteword"))?;
+                }
+
+                bytes.push(byte);
+            }
+        }
+    }
+
+    if bytes.len() < 4 {
+        Err(ErrorKind::InvalidUr("missing bytewords checksum"))?;
+    }
+
+    let split = bytes.len() - 4;
+    let mut checksum = [0; 4];
+
+    checksum.copy_from_slice(&bytes[split..]);
+    bytes.truncate(split);
+
+    if crc32(&bytes) != u32::from_be_bytes(checksum) {
+        Err(ErrorKind::InvalidUr("invalid bytewords checksum"))?;
+    }
+
+    Ok(bytes)
+}
+
+fn decode_pair(first: u8, last: u8) -> Result<u8, ErrorKind> {
+    pair_index(first, last)
+        .and_then(|idx| MINIMAL[idx])
+        .ok_or(ErrorKind::InvalidUr("invalid byteword"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn word_list() {
+        let mut sorted = WORDS.to_vec();
+
+        sorted.sort_unstable();
+        sorted.dedup();
+
+        assert_eq!(sorted, WORDS);
+        assert_eq!(MINIMAL.iter().filter(|byte| byte.is_some()).count(), 256);
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn crc32_vectors() {
+        assert_eq!(crc32(b"Hello, world!"), 0xebe6c6e6);
+        assert_eq!(crc32(b"Wolf"), 0x598c84dc);
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn reference_vector() {
+        let data = [0, 1, 2, 128, 255];
+        let standard = "able acid also lava zoom jade need echo taxi";
+        let uri = "able-acid-also-lava-zoom-jade-need-echo-taxi";
+        let minimal = "aeadaolazmjendeoti";
+
+        assert_eq!(&*encode(&data, Style::Standard), standard);
+        assert_eq!(&*encode(&data, Style::Uri), uri);
+        assert_eq!(&*encode(&data, Style::Minimal), minimal);
+        assert_eq!(&**decode(standard, Style::Standard).unwrap(), &data);
+        assert_eq!(&**decode(uri, Style::Uri).unwrap(), &data);
+        assert_eq!(&**decode(minimal, Style::Minimal).unwrap(), &data);
+        assert_eq!(
+            &**decode(&minimal.to_uppercase(), Style::Minimal).unwrap(),
+            &data
+        );
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn invalid() {
+        assert!(decode("aeadaolazmjendeot", Style::Minimal).is_err());
+        assert!(decode("aeadaolazmjendeotd", Style::Minimal).is_err());
+        assert!(decode("aeadaolazmjendeoaa", Style::Minimal).is_err());
+        assert!(decode("aeaeaeae", Style::Minimal).unwrap().is_empty());
+        assert!(decode("endeoti", Style::Minimal).is_err());
+        assert!(decode(
+            "able acid also lava zoom jade need echo tax",
+            Style::Standard
+        )
+        .is_err());
+        assert!(decode(
+            "able acid also lava zoom jade need echo tazi",
+            Style::Standard
+        )
+        .is_err());
+        assert!(decode("", Style::Standard).is_err());
+    }
+}
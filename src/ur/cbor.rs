@@ -0,0 +1,143 @@
+//! The small subset of CBOR needed for `crypto-bip39` and multipart fountain parts.
+
+use crate::error::ErrorKind;
+
+pub(crate) const UNSIGNED: u8 = 0;
+pub(crate) const BYTES: u8 = 2;
+pub(crate) const TEXT: u8 = 3;
+pub(crate) const ARRAY: u8 = 4;
+pub(crate) const MAP: u8 = 5;
+
+/// Number of bytes `write_head` produces for `value`
+pub(crate) fn head_len(value: u64) -> usize {
+    match value {
+        0..=23 => 1,
+        24..=0xFF => 2,
+        0x100..=0xFFFF => 3,
+        0x1_0000..=0xFFFF_FFFF => 5,
+        _ => 9,
+    }
+}
+
+/// Write a data item head in its shortest form
+pub(crate) fn write_head(out: &mut Vec<u8>, major: u8, value: u64) {
+    let major = major << 5;
+
+    match head_len(value) {
+        1 => out.push(major | value as u8),
+        2 => out.extend_from_slice(&[major | 24, value as u8]),
+        3 => {
+            out.push(major | 25);
+            out.extend_from_slice(&(value as u16).to_be_bytes());
+        }
+        5 => {
+            out.push(major | 26);
+            out.extend_from_slice(&(value as u32).to_be_bytes());
+        }
+        _ => {
+            out.push(major | 27);
+            out.extend_from_slice(&value.to_be_bytes());
+        }
+    }
+}
+
+pub(crate) struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Reader(bytes)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Read a data item head, rejecting indefinite lengths
+    pub fn head(&mut self) -> Result<(u8, u64), ErrorKind> {
+        let initial = self.take(1)?[0];
+        let major = initial >> 5;
+
+        let value = match initial & 0x1F {
+            info @ 0..=23 => info as u64,
+            24 => self.take(1)?[0] as u64,
+            25 => self.take(2)?.iter().fold(0, |acc, &b| acc << 8 | b as u64),
+            26 => self.take(4)?.iter().fold(0, |acc, &b| acc << 8 | b as u64),
+            27 => self.take(8)?.iter().fold(0, |acc, &b| acc << 8 | b as u64),
+            _ => return Err(ErrorKind::InvalidUr("unsupported CBOR")),
+        };
+
+        Ok((major, value))
+    }
+
+    /// Read a head of the given major type
+    pub fn expect(&mut self, major: u8) -> Result<u64, ErrorKind> {
+        match self.head()? {
+            (m, value) if m == major => Ok(value),
+            _ => Err(ErrorKind::InvalidUr("unexpected CBOR type")),
+        }
+    }
+
+    pub fn bytes(&mut self) -> Result<&'a [u8], ErrorKind> {
+        let len = self.expect(BYTES)?;
+
+        self.take_u64(len)
+    }
+
+    pub fn text(&mut self) -> Result<&'a str, ErrorKind> {
+        let len = self.expect(TEXT)?;
+
+        std::str::from_utf8(self.take_u64(len)?)
+            .map_err(|_| ErrorKind::InvalidUr("invalid CBOR text"))
+    }
+
+    fn take_u64(&mut self, len: u64) -> Result<&'a [u8], ErrorKind> {
+        if len > self.0.len() as u64 {
+            return Err(ErrorKind::InvalidUr("unexpected end of CBOR"));
+        }
+
+        self.take(len as usize)
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], ErrorKind> {
+        if self.0.len() < len {
+            return Err(ErrorKind::InvalidUr("unexpected end of CBOR"));
+        }
+
+        let (head, tail) = self.0.split_at(len);
+
+        self.0 = tail;
+        Ok(head)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn heads_round_trip() {
+        for &value in &[0, 23, 24, 255, 256, 65535, 65536, u32::MAX as u64, u64::MAX] {
+            let mut out = Vec::new();
+
+            write_head(&mut out, ARRAY, value);
+            assert_eq!(out.len(), head_len(value));
+            assert_eq!(Reader::new(&out).head().unwrap(), (ARRAY, value));
+        }
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn truncated() {
+        assert!(Reader::new(&[0x19, 0x01]).head().is_err());
+        assert!(Reader::new(&[0x62, b'e']).text().is_err());
+        assert!(
+            Reader::new(&[0x5B, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF])
+                .bytes()
+                .is_err()
+        );
+        assert!(Reader::new(&[0x9F]).head().is_err());
+    }
+}
@@ -0,0 +1,554 @@
+//! Fountain codes for multipart URs, as specified in [BCR-2020-005][bcr-2020-005].
+//!
+//! The message is split into `seq_len` equally sized fragments. The first `seq_len` parts carry
+//! one fragment each, every following part carries the XOR of a pseudo-random subset of
+//! fragments. The subset is chosen by a generator seeded from the part's sequence number and
+//! the message checksum, so both sides agree on it without it being transmitted.
+//!
+//! [bcr-2020-005]: https://github.com/BlockchainCommons/Research/blob/master/papers/bcr-2020-005-ur.md
+
+use super::bytewords::crc32;
+use super::cbor::{self, Reader};
+use crate::error::ErrorKind;
+use sha2::{Digest, Sha256};
+use std::mem;
+use zeroize::Zeroizing;
+
+/// Smallest fragment the encoder will produce
+const MIN_FRAGMENT_LEN: usize = 10;
+
+/// Largest message the decoder will accept, far above any mnemonic
+const MAX_MESSAGE_LEN: u64 = 0x1_0000;
+
+/// Xoshiro256** generator seeded with the SHA-256 hash of a byte string
+pub(crate) struct Xoshiro256 {
+    s: [u64; 4],
+}
+
+impl Xoshiro256 {
+    pub fn new(seed: &[u8]) -> Self {
+        let hash = Sha256::digest(seed);
+        let mut s = [0; 4];
+
+        for (word, chunk) in s.iter_mut().zip(hash.chunks(8)) {
+            *word = chunk.iter().fold(0, |acc, &b| acc << 8 | b as u64);
+        }
+
+        Xoshiro256 { s }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        let s = &mut self.s;
+        let result = s[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = s[1] << 17;
+
+        s[2] ^= s[0];
+        s[3] ^= s[1];
+        s[1] ^= s[2];
+        s[0] ^= s[3];
+        s[2] ^= t;
+        s[3] = s[3].rotate_left(45);
+
+        result
+    }
+
+    fn next_double(&mut self) -> f64 {
+        self.next_u64() as f64 / (u64::MAX as f64 + 1.0)
+    }
+
+    /// Uniform integer in `low..=high`
+    pub fn next_int(&mut self, low: u64, high: u64) -> u64 {
+        (self.next_double() * (high - low + 1) as f64) as u64 + low
+    }
+}
+
+/// Walker's alias method, constructed exactly as the reference implementation does so that the
+/// same random numbers yield the same samples
+struct Sampler {
+    probs: Vec<f64>,
+    aliases: Vec<usize>,
+}
+
+impl Sampler {
+    fn new(weights: &[f64]) -> Self {
+        let n = weights.len();
+        let sum: f64 = weights.iter().sum();
+        let mut p: Vec<f64> = weights.iter().map(|w| w * n as f64 / sum).collect();
+        let mut small = Vec::new();
+        let mut large = Vec::new();
+
+        for i in (0..n).rev() {
+            if p[i] < 1.0 {
+                small.push(i);
+            } else {
+                large.push(i);
+            }
+        }
+
+        let mut probs = vec![0.0; n];
+        let mut aliases = vec![0; n];
+
+        while !small.is_empty() && !large.is_empty() {
+            let a = small.pop().expect("not empty");
+            let g = large.pop().expect("not empty");
+
+            probs[a] = p[a];
+            aliases[a] = g;
+            p[g] += p[a] - 1.0;
+
+            if p[g] < 1.0 {
+                small.push(g);
+            } else {
+                large.push(g);
+            }
+        }
+
+        for i in large.into_iter().chain(small) {
+            probs[i] = 1.0;
+        }
+
+        Sampler { probs, aliases }
+    }
+
+    fn next(&self, rng: &mut Xoshiro256) -> usize {
+        let r1 = rng.next_double();
+        let r2 = rng.next_double();
+        let i = (self.probs.len() as f64 * r1) as usize;
+
+        if r2 < self.probs[i] {
+            i
+        } else {
+            self.aliases[i]
+        }
+    }
+}
+
+/// Number of fragments to mix, following the robust soliton-like `1/i` distribution
+fn choose_degree(seq_len: usize, rng: &mut Xoshiro256) -> usize {
+    let weights: Vec<f64> = (1..=seq_len).map(|i| 1.0 / i as f64).collect();
+
+    Sampler::new(&weights).next(rng) + 1
+}
+
+/// Sorted indexes of the fragments mixed into part `seq_num`
+pub(crate) fn choose_fragments(seq_num: u32, seq_len: usize, checksum: u32) -> Vec<usize> {
+    if seq_num as usize <= seq_len {
+        return vec![seq_num as usize - 1];
+    }
+
+    let mut seed = [0; 8];
+
+    seed[..4].copy_from_slice(&seq_num.to_be_bytes());
+    seed[4..].copy_from_slice(&checksum.to_be_bytes());
+
+    let mut rng = Xoshiro256::new(&seed);
+    let degree = choose_degree(seq_len, &mut rng);
+    let mut remaining: Vec<usize> = (0..seq_len).collect();
+    let mut chosen = Vec::with_capacity(degree);
+
+    while chosen.len() < degree {
+        let idx = rng.next_int(0, remaining.len() as u64 - 1) as usize;
+
+        chosen.push(remaining.remove(idx));
+    }
+
+    chosen.sort_unstable();
+    chosen
+}
+
+/// Smallest fragment count whose fragments fit in `max_fragment_len`
+fn nominal_fragment_len(message_len: usize, max_fragment_len: usize) -> usize {
+    let max_count = (message_len / MIN_FRAGMENT_LEN).max(1);
+
+    (1..=max_count)
+        .map(|count| message_len.div_ceil(count))
+        .find(|&len| len <= max_fragment_len)
+        .unwrap_or_else(|| message_len.div_ceil(max_count))
+}
+
+fn xor_into(target: &mut [u8], source: &[u8]) {
+    for (t, s) in target.iter_mut().zip(source) {
+        *t ^= s;
+    }
+}
+
+/// One part of a multipart UR, before bytewords encoding
+pub(crate) struct Part {
+    pub seq_num: u32,
+    pub seq_len: usize,
+    pub message_len: usize,
+    pub checksum: u32,
+    pub data: Zeroizing<Vec<u8>>,
+}
+
+impl Part {
+    pub fn to_cbor(&self) -> Zeroizing<Vec<u8>> {
+        let mut out = Zeroizing::new(Vec::with_capacity(
+            1 + 4 * 9 + cbor::head_len(self.data.len() as u64) + self.data.len(),
+        ));
+
+        cbor::write_head(&mut out, cbor::ARRAY, 5);
+        cbor::write_head(&mut out, cbor::UNSIGNED, self.seq_num as u64);
+        cbor::write_head(&mut out, cbor::UNSIGNED, self.seq_len as u64);
+        cbor::write_head(&mut out, cbor::UNSIGNED, self.message_len as u64);
+        cbor::write_head(&mut out, cbor::UNSIGNED, self.checksum as u64);
+        cbor::write_head(&mut out, cbor::BYTES, self.data.len() as u64);
+        out.extend_from_slice(&self.data);
+        out
+    }
+
+    pub fn from_cbor(bytes: &[u8]) -> Result<Part, ErrorKind> {
+        let mut reader = Reader::new(bytes);
+
+        if reader.expect(cbor::ARRAY)? != 5 {
+            return Err(ErrorKind::InvalidUr("invalid multipart part"));
+        }
+
+        let seq_num = reader.expect(cbor::UNSIGNED)?;
+        let seq_len = reader.expect(cbor::UNSIGNED)?;
+        let message_len = reader.expect(cbor::UNSIGNED)?;
+        let checksum = reader.expect(cbor::UNSIGNED)?;
+        let data = Zeroizing::new(reader.bytes()?.to_vec());
+
+        if !reader.is_empty()
+            || seq_num == 0
+            || seq_num > u32::MAX as u64
+            || seq_len == 0
+            || checksum > u32::MAX as u64
+            || data.is_empty()
+            || message_len > MAX_MESSAGE_LEN
+            || seq_len != message_len.div_ceil(data.len() as u64)
+        {
+            return Err(ErrorKind::InvalidUr("invalid multipart part"));
+        }
+
+        Ok(Part {
+            seq_num: seq_num as u32,
+            seq_len: seq_len as usize,
+            message_len: message_len as usize,
+            checksum: checksum as u32,
+            data,
+        })
+    }
+}
+
+pub(crate) struct Encoder {
+    fragments: Vec<Zeroizing<Vec<u8>>>,
+    message_len: usize,
+    checksum: u32,
+    seq_num: u32,
+}
+
+impl Encoder {
+    pub fn new(message: &[u8], max_fragment_len: usize) -> Self {
+        let fragment_len = nominal_fragment_len(message.len(), max_fragment_len.max(1));
+        let fragments = message
+            .chunks(fragment_len)
+            .map(|chunk| {
+                let mut fragment = Zeroizing::new(vec![0; fragment_len]);
+
+                fragment[..chunk.len()].copy_from_slice(chunk);
+                fragment
+            })
+            .collect();
+
+        Encoder {
+            fragments,
+            message_len: message.len(),
+            checksum: crc32(message),
+            seq_num: 0,
+        }
+    }
+
+    pub fn seq_len(&self) -> usize {
+        self.fragments.len()
+    }
+
+    pub fn next_part(&mut self) -> Part {
+        self.seq_num = self.seq_num.wrapping_add(1).max(1);
+
+        let mut data = Zeroizing::new(vec![0; self.fragments[0].len()]);
+
+        for idx in choose_fragments(self.seq_num, self.seq_len(), self.checksum) {
+            xor_into(&mut data, &self.fragments[idx]);
+        }
+
+        Part {
+            seq_num: self.seq_num,
+            seq_len: self.seq_len(),
+            message_len: self.message_len,
+            checksum: self.checksum,
+            data,
+        }
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct Decoder {
+    /// `(seq_len, message_len, checksum, fragment_len)` of the first part received
+    params: Option<(usize, usize, u32, usize)>,
+    fragments: Vec<Option<Zeroizing<Vec<u8>>>>,
+    recovered: usize,
+    mixed: Vec<(Vec<usize>, Zeroizing<Vec<u8>>)>,
+}
+
+impl Decoder {
+    /// Number of fragments recovered, and the total number of fragments, once known
+    pub fn progress(&self) -> Option<(usize, usize)> {
+        self.params.map(|(seq_len, ..)| (self.recovered, seq_len))
+    }
+
+    /// Feed a part, returning the message once every fragment has been recovered
+    pub fn receive(&mut self, part: Part) -> Result<Option<Zeroizing<Vec<u8>>>, ErrorKind> {
+        let params = (
+            part.seq_len,
+            part.message_len,
+            part.checksum,
+            part.data.len(),
+        );
+
+        match self.params {
+            None => {
+                self.params = Some(params);
+                self.fragments = (0..part.seq_len).map(|_| None).collect();
+            }
+            Some(expected) if expected != params => {
+                return Err(ErrorKind::InvalidUr("part belongs to a different message"));
+            }
+            _ => (),
+        }
+
+        let mut queue = vec![(
+            choose_fragments(part.seq_num, part.seq_len, part.checksum),
+            part.data,
+        )];
+
+        while let Some((mut indexes, mut data)) = queue.pop() {
+            // Strip every fragment we already know
+            let fragments = &self.fragments;
+            indexes.retain(|&idx| match fragments[idx] {
+                Some(ref fragment) => {
+                    xor_into(&mut data, fragment);
+                    false
+                }
+                None => true,
+            });
+
+            // Strip mixed parts covering a strict subset of this one
+            for (other, other_data) in &self.mixed {
+                if other.len() < indexes.len() && other.iter().all(|idx| indexes.contains(idx)) {
+                    xor_into(&mut data, other_data);
+                    indexes.retain(|idx| !other.contains(idx));
+                }
+            }
+
+            match indexes.len() {
+                0 => (),
+                1 => {
+                    let idx = indexes[0];
+
+                    self.fragments[idx] = Some(data);
+                    self.recovered += 1;
+
+                    // Mixed parts containing the new fragment can now be reduced
+                    let (reducible, rest) = mem::take(&mut self.mixed)
+                        .into_iter()
+                        .partition(|(other, _)| other.contains(&idx));
+
+                    self.mixed = rest;
+                    queue.extend(reducible);
+                }
+                _ => {
+                    if self.mixed.iter().any(|(other, _)| *other == indexes) {
+                        continue;
+                    }
+
+                    let (reducible, rest) = mem::take(&mut self.mixed).into_iter().partition(
+                        |(other, _): &(Vec<usize>, _)| {
+                            other.len() > indexes.len()
+                                && indexes.iter().all(|idx| other.contains(idx))
+                        },
+                    );
+
+                    self.mixed = rest;
+                    self.mixed.push((indexes, data));
+                    queue.extend(reducible);
+                }
+            }
+        }
+
+        if self.recovered < self.fragments.len() {
+            return Ok(None);
+        }
+
+        let (_, message_len, checksum, fragment_len) = params;
+        let mut message = Zeroizing::new(Vec::with_capacity(self.fragments.len() * fragment_len));
+
+        for fragment in self.fragments.iter().flatten() {
+            message.extend_from_slice(fragment);
+        }
+
+        message.truncate(message_len);
+
+        if crc32(&message) != checksum {
+            return Err(ErrorKind::InvalidUr("invalid multipart checksum"));
+        }
+
+        Ok(Some(message))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    /// Deterministic test message, as generated by the reference implementations
+    fn make_message(len: usize) -> Vec<u8> {
+        let mut rng = Xoshiro256::new(b"Wolf");
+
+        (0..len).map(|_| rng.next_int(0, 255) as u8).collect()
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn xoshiro_reference() {
+        let mut rng = Xoshiro256::new(b"Wolf");
+        let numbers: Vec<u64> = (0..12).map(|_| rng.next_u64() % 100).collect();
+
+        assert_eq!(numbers, [42, 81, 85, 8, 82, 84, 76, 73, 70, 88, 2, 74]);
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn degree_reference() {
+        let fragment_len = nominal_fragment_len(1024, 100);
+        let seq_len = 1024usize.div_ceil(fragment_len);
+        let degrees: Vec<usize> = (1..=30)
+            .map(|nonce| {
+                let mut rng = Xoshiro256::new(format!("Wolf-{}", nonce).as_bytes());
+
+                choose_degree(seq_len, &mut rng)
+            })
+            .collect();
+
+        assert_eq!(
+            degrees,
+            [
+                11, 3, 6, 5, 2, 1, 2, 11, 1, 3, 9, 10, 10, 4, 2, 1, 1, 2, 1, 1, 5, 2, 4, 10, 3, 2,
+                1, 1, 3, 11
+            ]
+        );
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn fragments_reference() {
+        let message = make_message(1024);
+        let checksum = crc32(&message);
+        let seq_len = 1024usize.div_ceil(nominal_fragment_len(1024, 100));
+        let chosen: Vec<Vec<usize>> = (11..=20)
+            .map(|seq_num| choose_fragments(seq_num, seq_len, checksum))
+            .collect();
+
+        assert_eq!(seq_len, 11);
+        assert_eq!(
+            chosen,
+            [
+                vec![10],
+                vec![9],
+                vec![2, 5, 6, 8, 9, 10],
+                vec![8],
+                vec![1, 5],
+                vec![1],
+                vec![0, 2, 4, 5, 8, 10],
+                vec![5],
+                vec![2],
+                vec![2],
+            ]
+        );
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn encoder_reference() {
+        let message = make_message(256);
+        let mut encoder = Encoder::new(&message, 30);
+        let part = encoder.next_part();
+
+        assert_eq!(encoder.seq_len(), 9);
+        assert_eq!(part.checksum, 23570951);
+        assert_eq!(
+            hex::encode(&*part.data),
+            "916ec65cf77cadf55cd7f9cda1a1030026ddd42e905b77adc36e4f2d3c"
+        );
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn decode_from_mixed_parts() {
+        let message = make_message(1024);
+        let mut encoder = Encoder::new(&message, 100);
+        let mut decoder = Decoder::default();
+
+        // Skip every simple part, so the message has to be recovered from mixtures alone
+        for _ in 0..encoder.seq_len() {
+            encoder.next_part();
+        }
+
+        let decoded = loop {
+            let part = encoder.next_part();
+
+            assert!(part.seq_num < 100, "decoder should have finished by now");
+
+            if let Some(decoded) = decoder.receive(part).unwrap() {
+                break decoded;
+            }
+        };
+
+        assert_eq!(&*decoded, &message);
+        assert_eq!(decoder.progress(), Some((11, 11)));
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn part_cbor_round_trip() {
+        let message = make_message(256);
+        let part = Encoder::new(&message, 30).next_part();
+        let decoded = Part::from_cbor(&part.to_cbor()).unwrap();
+
+        assert_eq!(decoded.seq_num, 1);
+        assert_eq!(decoded.seq_len, 9);
+        assert_eq!(decoded.message_len, 256);
+        assert_eq!(decoded.checksum, part.checksum);
+        assert_eq!(decoded.data, part.data);
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn mismatched_parts() {
+        let mut decoder = Decoder::default();
+
+        decoder
+            .receive(Encoder::new(&make_message(256), 30).next_part())
+            .unwrap();
+
+        assert!(decoder
+            .receive(Encoder::new(&make_message(255), 30).next_part())
+            .is_err());
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn inconsistent_seq_len() {
+        let mut part = Encoder::new(&make_message(256), 30).next_part();
+
+        // A huge sequence length must not make the decoder allocate for it
+        part.seq_len = u32::MAX as usize;
+        assert!(Part::from_cbor(&part.to_cbor()).is_err());
+
+        part.seq_len = 8;
+        assert!(Part::from_cbor(&part.to_cbor()).is_err());
+    }
+}
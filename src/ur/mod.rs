@@ -0,0 +1,480 @@
+//! [Uniform Resources][ur] encoding of a [`Mnemonic`][Mnemonic] as `ur:crypto-bip39`.
+//!
+//! The mnemonic is encoded as the CBOR map `{1: [words], 2: language}` specified in
+//! [BCR-2020-006][crypto-bip39], then written out with [`bytewords`][bytewords]. Short enough
+//! payloads fit in a single part, while animated QR codes use multipart URs produced by an
+//! [`Encoder`][Encoder] and reassembled by a [`Decoder`][Decoder]:
+//!
+//! ```
+//! use bip39::{Mnemonic, Language};
+//! use bip39::ur::Decoder;
+//!
+//! let phrase = "shield group erode awake lock sausage cash glare wave crew flame glove";
+//! let mnemonic = Mnemonic::from_phrase(phrase, Language::English).unwrap();
+//!
+//! // Single part
+//! let ur = mnemonic.to_ur().unwrap();
+//!
+//! assert!(ur.starts_with("ur:crypto-bip39/oeadlkiy"));
+//! assert_eq!(Mnemonic::from_ur(&ur).unwrap(), mnemonic);
+//!
+//! // Multipart, the decoder accepts parts in any order and ignores duplicates
+//! let mut encoder = mnemonic.to_ur_encoder(20).unwrap();
+//! let mut decoder = Decoder::new();
+//!
+//! let decoded = loop {
+//!     let part = encoder.next_part();
+//!
+//!     if let Some(decoded) = decoder.receive(&part).unwrap() {
+//!         break decoded;
+//!     }
+//! };
+//!
+//! assert_eq!(decoded, mnemonic);
+//! ```
+//!
+//! [ur]: https://github.com/BlockchainCommons/Research/blob/master/papers/bcr-2020-005-ur.md
+//! [crypto-bip39]: https://github.com/BlockchainCommons/Research/blob/master/papers/bcr-2020-006-urtypes.md
+//! [Mnemonic]: ../mnemonic/struct.Mnemonic.html
+//! [bytewords]: ./bytewords/index.html
+//! [Encoder]: ./struct.Encoder.html
+//! [Decoder]: ./struct.Decoder.html
+
+pub mod bytewords;
+mod cbor;
+mod fountain;
+
+use self::bytewords::Style;
+use self::cbor::Reader;
+use crate::error::ErrorKind;
+use crate::language::Language;
+use crate::mnemonic::Mnemonic;
+use crate::util::push_str;
+use anyhow::Error;
+use std::fmt;
+use zeroize::Zeroizing;
+
+/// UR type of an encoded [`Mnemonic`][Mnemonic]
+///
+/// [Mnemonic]: ../mnemonic/struct.Mnemonic.html
+pub const UR_TYPE: &str = "crypto-bip39";
+
+const SCHEME: &str = "ur:";
+const KEY_WORDS: u64 = 1;
+const KEY_LANG: u64 = 2;
+
+impl Mnemonic {
+    /// Encode the mnemonic as a single part `ur:crypto-bip39` string
+    ///
+    /// Mnemonics in a [custom language][custom] are rejected with an `Error` of kind
+    /// `ErrorKind::InvalidUr`, as no decoder would know their language code.
+    ///
+    /// [custom]: ./enum.Language.html#method.custom
+    pub fn to_ur(&self) -> Result<Zeroizing<String>, Error> {
+        Ok(single_part(&self.to_crypto_bip39()?))
+    }
+
+    /// Decode a mnemonic from a single part `ur:crypto-bip39` string
+    ///
+    /// The string is matched case insensitively, as QR codes often carry it upper case. Use a
+    /// [`Decoder`][Decoder] for multipart URs.
+    ///
+    /// [Decoder]: ./ur/struct.Decoder.html
+    pub fn from_ur(ur: &str) -> Result<Mnemonic, Error> {
+        match parse(ur)? {
+            (None, body) => Mnemonic::from_crypto_bip39(&bytewords::decode(body, Style::Minimal)?),
+            (Some(_), _) => Err(ErrorKind::InvalidUr("multipart UR, use ur::Decoder"))?,
+        }
+    }
+
+    /// Create an [`Encoder`][Encoder] producing multipart `ur:crypto-bip39` strings, each
+    /// carrying at most `max_fragment_len` bytes of the message
+    ///
+    /// Fails for custom languages, like [`Mnemonic::to_ur()`][Mnemonic::to_ur()].
+    ///
+    /// [Encoder]: ./ur/struct.Encoder.html
+    /// [Mnemonic::to_ur()]: ./mnemonic/struct.Mnemonic.html#method.to_ur
+    pub fn to_ur_encoder(&self, max_fragment_len: usize) -> Result<Encoder, Error> {
+        let message = self.to_crypto_bip39()?;
+
+        Ok(Encoder {
+            fountain: fountain::Encoder::new(&message, max_fragment_len),
+            message,
+        })
+    }
+
+    fn to_crypto_bip39(&self) -> Result<Zeroizing<Vec<u8>>, ErrorKind> {
+        if let Language::Custom(_) = self.language() {
+            return Err(ErrorKind::InvalidUr("custom languages can't be encoded"));
+        }
+
+        let code = self.language().language_code();
+        let words = self.phrase().split(' ');
+        let len = 2
+            + cbor::head_len(words.clone().count() as u64)
+            + words
+                .clone()
+                .map(|word| cbor::head_len(word.len() as u64) + word.len())
+                .sum::<usize>()
+            + 1
+            + cbor::head_len(code.len() as u64)
+            + code.len();
+
+        let mut out = Zeroizing::new(Vec::with_capacity(len));
+
+        cbor::write_head(&mut out, cbor::MAP, 2);
+        cbor::write_head(&mut out, cbor::UNSIGNED, KEY_WORDS);
+        cbor::write_head(&mut out, cbor::ARRAY, words.clone().count() as u64);
+
+        for word in words {
+            cbor::write_head(&mut out, cbor::TEXT, word.len() as u64);
+            out.extend_from_slice(word.as_bytes());
+        }
+
+        cbor::write_head(&mut out, cbor::UNSIGNED, KEY_LANG);
+        cbor::write_head(&mut out, cbor::TEXT, code.len() as u64);
+        out.extend_from_slice(code.as_bytes());
+
+        debug_assert_eq!(out.len(), len, "buffer must not reallocate");
+
+        Ok(out)
+    }
+
+    fn from_crypto_bip39(message: &[u8]) -> Result<Mnemonic, Error> {
+        let mut reader = Reader::new(message);
+        let mut phrase = Zeroizing::new(String::with_capacity(message.len()));
+        let mut lang = Some(Language::English);
+        let mut has_words = false;
+
+        for _ in 0..reader.expect(cbor::MAP)? {
+            match reader.expect(cbor::UNSIGNED)? {
+                KEY_WORDS if !has_words => {
+                    has_words = true;
+
+                    for idx in 0..reader.expect(cbor::ARRAY)? {
+                        if idx > 0 {
+                            push_str(&mut phrase, " ");
+                        }
+
+                        push_str(&mut phrase, reader.text()?);
+                    }
+                }
+                KEY_LANG => lang = Language::from_language_code(reader.text()?),
+                _ => Err(ErrorKind::InvalidUr("invalid crypto-bip39"))?,
+            }
+        }
+
+        if !has_words || !reader.is_empty() {
+            Err(ErrorKind::InvalidUr("invalid crypto-bip39"))?;
+        }
+
+        let lang = lang.ok_or(ErrorKind::InvalidUr("unsupported language"))?;
+
+        Mnemonic::from_phrase(&phrase, lang)
+    }
+}
+
+/// Produces an endless stream of multipart URs for a [`Mnemonic`][Mnemonic], see
+/// [`Mnemonic::to_ur_encoder()`][Mnemonic::to_ur_encoder()].
+///
+/// The first [`seq_len()`][seq_len] parts each carry one fragment of the message, the rest are
+/// fountain coded mixtures of fragments, so a scanner that misses some frames of an animated QR
+/// code can still complete the message.
+///
+/// [Mnemonic]: ../mnemonic/struct.Mnemonic.html
+/// [Mnemonic::to_ur_encoder()]: ../mnemonic/struct.Mnemonic.html#method.to_ur_encoder
+/// [seq_len]: ./struct.Encoder.html#method.seq_len
+pub struct Encoder {
+    fountain: fountain::Encoder,
+    message: Zeroizing<Vec<u8>>,
+}
+
+impl Encoder {
+    /// Number of fragments the message was split into
+    pub fn seq_len(&self) -> usize {
+        self.fountain.seq_len()
+    }
+
+    /// Produce the next part. If the message fits in a single fragment this is always the
+    /// single part UR.
+    pub fn next_part(&mut self) -> Zeroizing<String> {
+        if self.seq_len() == 1 {
+            return single_part(&self.message);
+        }
+
+        let part = self.fountain.next_part();
+
+        multi_part(part.seq_num, part.seq_len, &part.to_cbor())
+    }
+}
+
+impl fmt::Debug for Encoder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Encoder")
+            .field("seq_len", &self.seq_len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Reassembles a [`Mnemonic`][Mnemonic] from `ur:crypto-bip39` parts, in any order.
+///
+/// [Mnemonic]: ../mnemonic/struct.Mnemonic.html
+#[derive(Default)]
+pub struct Decoder {
+    fountain: fountain::Decoder,
+}
+
+impl Decoder {
+    pub fn new() -> Self {
+        Decoder::default()
+    }
+
+    /// Feed a scanned part, single or multipart, returning the mnemonic once it is complete
+    ///
+    /// Parts that are malformed, of another UR type, or belong to a different message return an
+    /// `Error` of kind `ErrorKind::InvalidUr` and are otherwise ignored.
+    pub fn receive(&mut self, part: &str) -> Result<Option<Mnemonic>, Error> {
+        let (seq, body) = parse(part)?;
+        let payload = bytewords::decode(body, Style::Minimal)?;
+
+        let (seq_num, seq_len) = match seq {
+            Some(seq) => seq,
+            None => return Mnemonic::from_crypto_bip39(&payload).map(Some),
+        };
+
+        let part = fountain::Part::from_cbor(&payload)?;
+
+        if part.seq_num != seq_num || part.seq_len != seq_len {
+            Err(ErrorKind::InvalidUr(
+                "part sequence does not match its header",
+            ))?;
+        }
+
+        match self.fountain.receive(part)? {
+            Some(message) => Mnemonic::from_crypto_bip39(&message).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Fragments recovered so far and the total number of fragments, once the first multipart
+    /// part has been received
+    pub fn progress(&self) -> Option<(usize, usize)> {
+        self.fountain.progress()
+    }
+}
+
+impl fmt::Debug for Decoder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Decoder")
+            .field("progress", &self.progress())
+            .finish_non_exhaustive()
+    }
+}
+
+fn single_part(message: &[u8]) -> Zeroizing<String> {
+    let body = bytewords::encode(message, Style::Minimal);
+    let mut ur = Zeroizing::new(String::with_capacity(
+        SCHEME.len() + UR_TYPE.len() + 1 + body.len(),
+    ));
+
+    push_str(&mut ur, SCHEME);
+    push_str(&mut ur, UR_TYPE);
+    push_str(&mut ur, "/");
+    push_str(&mut ur, &body);
+    ur
+}
+
+fn multi_part(seq_num: u32, seq_len: usize, part: &[u8]) -> Zeroizing<String> {
+    let body = bytewords::encode(part, Style::Minimal);
+    let seq = format!("{}-{}/", seq_num, seq_len);
+    let mut ur = Zeroizing::new(String::with_capacity(
+        SCHEME.len() + UR_TYPE.len() + 1 + seq.len() + body.len(),
+    ));
+
+    push_str(&mut ur, SCHEME);
+    push_str(&mut ur, UR_TYPE);
+    push_str(&mut ur, "/");
+    push_str(&mut ur, &seq);
+    push_str(&mut ur, &body);
+    ur
+}
+
+/// `(seq_num, seq_len)` of a multipart UR
+type Sequence = (u32, usize);
+
+/// Split a UR into its optional sequence and bytewords body
+fn parse(ur: &str) -> Result<(Option<Sequence>, &str), ErrorKind> {
+    let (scheme, path) = ur.split_once(':').ok_or(ErrorKind::InvalidUr("not a UR"))?;
+
+    if !scheme.eq_ignore_ascii_case(&SCHEME[..SCHEME.len() - 1]) {
+        Err(ErrorKind::InvalidUr("not a UR"))?;
+    }
+
+    let mut components = path.split('/');
+    let ty = components.next().unwrap_or_default();
+
+    if !ty.eq_ignore_ascii_case(UR_TYPE) {
+        Err(ErrorKind::InvalidUr("unexpected UR type"))?;
+    }
+
+    match (components.next(), components.next(), components.next()) {
+        (Some(body), None, None) => Ok((None, body)),
+        (Some(seq), Some(body), None) => {
+            let mut numbers = seq.splitn(2, '-').map(|n| n.parse().ok());
+
+            match (numbers.next().flatten(), numbers.next().flatten()) {
+                (Some(seq_num), Some(seq_len)) => Ok((Some((seq_num, seq_len as usize)), body)),
+                _ => Err(ErrorKind::InvalidUr("malformed UR sequence")),
+            }
+        }
+        _ => Err(ErrorKind::InvalidUr("malformed UR")),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::wordlist::CustomWordList;
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    /// Example from BCR-2020-006
+    const PHRASE: &str = "shield group erode awake lock sausage cash glare wave crew flame glove";
+
+    fn mnemonic() -> Mnemonic {
+        Mnemonic::from_phrase(PHRASE, Language::English).unwrap()
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn crypto_bip39_cbor() {
+        let cbor = mnemonic().to_crypto_bip39().unwrap();
+        let mut expected = vec![0xA2, 0x01, 0x8C];
+
+        for word in PHRASE.split(' ') {
+            expected.push(0x60 + word.len() as u8);
+            expected.extend_from_slice(word.as_bytes());
+        }
+
+        expected.extend_from_slice(&[0x02, 0x62, b'e', b'n']);
+
+        assert_eq!(&*cbor, &expected);
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn single_part_round_trip() {
+        let ur = mnemonic().to_ur().unwrap();
+
+        assert_eq!(&*ur, "ur:crypto-bip39/oeadlkiyjkisinihjzieihiojpjlkpjoihihjpjlieihihhskthsjeihiejzjliajeiojkhskpjkhsioihieiahsjkisihiojzhsjpihiekthskoihieiajpihktihiyjzhsjnihihiojzjlkoihaoidihjtrkkndede");
+        assert_eq!(Mnemonic::from_ur(&ur).unwrap(), mnemonic());
+        assert_eq!(Mnemonic::from_ur(&ur.to_uppercase()).unwrap(), mnemonic());
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn multi_part_round_trip() {
+        let mut encoder = mnemonic().to_ur_encoder(20).unwrap();
+        let mut decoder = Decoder::new();
+
+        assert_eq!(encoder.seq_len(), 4);
+
+        let parts: Vec<_> = (0..12).map(|_| encoder.next_part()).collect();
+
+        assert!(parts[0].starts_with("ur:crypto-bip39/1-4/"));
+        assert!(parts[11].starts_with("ur:crypto-bip39/12-4/"));
+
+        // Lose the first two simple parts, the mixed parts make up for them
+        let mut result = None;
+
+        for part in &parts[2..] {
+            if let Some(mnemonic) = decoder.receive(part).unwrap() {
+                result = Some(mnemonic);
+                break;
+            }
+        }
+
+        assert_eq!(result.unwrap(), mnemonic());
+        assert_eq!(decoder.progress(), Some((4, 4)));
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn single_fragment_encoder() {
+        let mut encoder = mnemonic().to_ur_encoder(1000).unwrap();
+
+        assert_eq!(encoder.seq_len(), 1);
+        assert_eq!(encoder.next_part(), mnemonic().to_ur().unwrap());
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg(feature = "japanese")]
+    fn language_round_trip() {
+        let mnemonic = Mnemonic::from_entropy(&[0x42; 16], Language::Japanese).unwrap();
+
+        assert_eq!(
+            Mnemonic::from_ur(&mnemonic.to_ur().unwrap()).unwrap(),
+            mnemonic
+        );
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn custom_language_rejected() {
+        let words: Vec<String> = (0..2048).map(|idx| format!("w{:04}", idx)).collect();
+        let list = CustomWordList::new("x-ur", &words.join(" ")).unwrap();
+        let mnemonic = Mnemonic::from_entropy(&[0; 16], Language::custom(list).unwrap()).unwrap();
+
+        assert!(mnemonic.to_ur().is_err());
+        assert!(mnemonic.to_ur_encoder(20).is_err());
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn invalid() {
+        let ur = mnemonic().to_ur().unwrap();
+
+        assert!(Mnemonic::from_ur(&ur.replace("crypto-bip39", "bytes")).is_err());
+        assert!(Mnemonic::from_ur(&ur.replace("ur:", "")).is_err());
+        assert!(Mnemonic::from_ur(&ur[..ur.len() - 2]).is_err());
+        assert!(Mnemonic::from_ur(&ur.replace("oeadlkiy", "oeadlkiz")).is_err());
+        assert!(Mnemonic::from_ur(&mnemonic().to_ur_encoder(20).unwrap().next_part()).is_err());
+        assert!(Decoder::new()
+            .receive("ur:crypto-bip39/1-x/aeaeaeae")
+            .is_err());
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn multipart_bytes_reference() {
+        // First parts of the `ur:bytes` multipart vector shared by the reference implementations
+        let mut rng = fountain::Xoshiro256::new(b"Wolf");
+        let mut message = vec![0x59, 0x01, 0x00];
+
+        message.extend((0..256).map(|_| rng.next_int(0, 255) as u8));
+
+        let mut encoder = fountain::Encoder::new(&message, 30);
+        let parts: Vec<String> = (0..3)
+            .map(|_| {
+                let part = encoder.next_part();
+
+                format!(
+                    "ur:bytes/{}-{}/{}",
+                    part.seq_num,
+                    part.seq_len,
+                    *bytewords::encode(&part.to_cbor(), Style::Minimal)
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            parts,
+            [
+                "ur:bytes/1-9/lpadascfadaxcywenbpljkhdcahkadaemejtswhhylkepmykhhtsytsnoyoyaxaedsuttydmmhhpktpmsrjtdkgslpgh",
+                "ur:bytes/2-9/lpaoascfadaxcywenbpljkhdcagwdpfnsboxgwlbaawzuefywkdplrsrjynbvygabwjldapfcsgmghhkhstlrdcxaefz",
+                "ur:bytes/3-9/lpaxascfadaxcywenbpljkhdcahelbknlkuejnbadmssfhfrdpsbiegecpasvssovlgeykssjykklronvsjksopdzmol",
+            ]
+        );
+    }
+}
@@ -1,28 +1,31 @@
+use std::mem;
 use unicode_normalization::Decompositions;
+use zeroize::{Zeroize, Zeroizing};
 
 pub(crate) trait IterExt: Iterator {
-    fn join<R>(&mut self, glue: &str) -> R
+    /// Join the items with `glue`. The buffer never reallocates without zeroing the previous
+    /// allocation, so no partial copies of the output are left behind in freed memory.
+    fn join(&mut self, glue: &str) -> Zeroizing<String>
     where
-        R: From<String>,
         Self::Item: Joinable,
     {
         let first = match self.next() {
             Some(first) => first,
-            None => return String::new().into(),
+            None => return Zeroizing::new(String::new()),
         };
 
         let (lower, _) = self.size_hint();
 
-        let mut buffer = String::with_capacity(lower * (10 + glue.len()));
+        let mut buffer = Zeroizing::new(String::with_capacity((lower + 1) * (10 + glue.len())));
 
         first.write_into(&mut buffer);
 
         for item in self {
-            buffer.push_str(glue);
+            push_str(&mut buffer, glue);
             item.write_into(&mut buffer);
         }
 
-        buffer.into()
+        buffer
     }
 
     fn bits<Out>(self) -> BitIter<Self::Item, Out, Self>
@@ -36,21 +39,41 @@ pub(crate) trait IterExt: Iterator {
 }
 
 pub(crate) trait Joinable {
-    fn write_into(self, buf: &mut String);
+    fn write_into(self, buf: &mut Zeroizing<String>);
 }
 
 /// Allow iterator joining on str slices
 impl Joinable for &str {
-    fn write_into(self, buf: &mut String) {
-        buf.push_str(self);
+    fn write_into(self, buf: &mut Zeroizing<String>) {
+        push_str(buf, self);
     }
 }
 
 /// Allow iterator joining on unicode_normalization iterators
 impl<I: Iterator<Item = char>> Joinable for Decompositions<I> {
-    fn write_into(self, buf: &mut String) {
-        buf.extend(self);
+    fn write_into(self, buf: &mut Zeroizing<String>) {
+        let mut encoded = [0u8; 4];
+
+        for ch in self {
+            push_str(buf, ch.encode_utf8(&mut encoded));
+        }
+
+        encoded.zeroize();
+    }
+}
+
+/// Append to a secret string, moving it to a bigger allocation by hand when it runs out of
+/// capacity so that the old allocation is zeroed rather than freed as is.
+pub(crate) fn push_str(buf: &mut Zeroizing<String>, s: &str) {
+    if buf.capacity() - buf.len() < s.len() {
+        let capacity = (buf.len() + s.len()).max(buf.capacity() * 2);
+        let mut grown = Zeroizing::new(String::with_capacity(capacity));
+
+        grown.push_str(buf);
+        *buf = grown;
     }
+
+    buf.push_str(s);
 }
 
 impl<I: Iterator> IterExt for I {}
@@ -69,7 +92,7 @@ impl Bits for u8 {
     }
 }
 
-impl<'a> Bits for &'a u8 {
+impl Bits for &u8 {
     const SIZE: usize = 8;
 
     fn bits(self) -> u32 {
@@ -77,6 +100,7 @@ impl<'a> Bits for &'a u8 {
     }
 }
 
+/// The 11-bit value of a single word, convert to and from `u16` with `From`
 #[derive(Clone, Copy, Debug)]
 pub struct Bits11(u16);
 
@@ -100,6 +124,8 @@ impl From<Bits11> for u16 {
     }
 }
 
+#[derive(Zeroize)]
+#[zeroize(drop)]
 pub(crate) struct BitWriter {
     offset: usize,
     remainder: u32,
@@ -108,11 +134,7 @@ pub(crate) struct BitWriter {
 
 impl BitWriter {
     pub fn with_capacity(capacity: usize) -> Self {
-        let mut bytes = capacity / 8;
-
-        if capacity % 8 != 0 {
-            bytes += 1;
-        }
+        let bytes = capacity.div_ceil(8);
 
         Self {
             offset: 0,
@@ -128,22 +150,35 @@ impl BitWriter {
         self.offset += B::SIZE;
 
         while self.offset >= 8 {
-            self.inner.push((self.remainder >> 24) as u8);
+            self.push_byte((self.remainder >> 24) as u8);
             self.remainder <<= 8;
             self.offset -= 8;
         }
     }
 
+    fn push_byte(&mut self, byte: u8) {
+        if self.inner.len() == self.inner.capacity() {
+            // Same as `push_str`, never let `Vec` reallocate the secret bytes on its own
+            let mut grown = Vec::with_capacity((self.inner.capacity() * 2).max(8));
+
+            grown.extend_from_slice(&self.inner);
+            self.inner.zeroize();
+            self.inner = grown;
+        }
+
+        self.inner.push(byte);
+    }
+
     pub fn len(&self) -> usize {
         self.inner.len() * 8 + self.offset
     }
 
-    pub fn into_bytes(mut self) -> Vec<u8> {
+    pub fn into_bytes(mut self) -> Zeroizing<Vec<u8>> {
         if self.offset != 0 {
-            self.inner.push((self.remainder >> 24) as u8);
+            self.push_byte((self.remainder >> 24) as u8);
         }
 
-        self.inner
+        Zeroizing::new(mem::take(&mut self.inner))
     }
 }
 
@@ -0,0 +1,392 @@
+//! Checks for mnemonics that are valid, but not safe to hold funds.
+//!
+//! A phrase copied from documentation or a test vector passes every checksum, and so does one
+//! made up by hand or generated from broken entropy. None of these can be detected with
+//! certainty, but [`Mnemonic::weakness_report()`][weakness_report] flags the common cases, both
+//! when a phrase is imported and right after one is generated.
+//!
+//! [weakness_report]: ../mnemonic/struct.Mnemonic.html#method.weakness_report
+
+use crate::mnemonic::Mnemonic;
+use std::fmt;
+use subtle::{Choice, ConstantTimeEq};
+
+/// Entropy of published phrases: the Trezor reference vectors in `tests/standard-vectors.rs`,
+/// the phrases in this crate's documentation, tests and benchmarks, including the SeedQR and UR
+/// specification examples, and the default phrases of common development tools. The phrase in
+/// the `random_phrase_is_not_weak` test is left out on purpose, as it is what the test checks.
+const KNOWN_ENTROPY: &[&str] = &[
+    "00000000000000000000000000000000",
+    "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+    "80808080808080808080808080808080",
+    "ffffffffffffffffffffffffffffffff",
+    "000000000000000000000000000000000000000000000000",
+    "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+    "808080808080808080808080808080808080808080808080",
+    "ffffffffffffffffffffffffffffffffffffffffffffffff",
+    "0000000000000000000000000000000000000000000000000000000000000000",
+    "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+    "8080808080808080808080808080808080808080808080808080808080808080",
+    "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+    "9e885d952ad362caeb4efe34a8e91bd2",
+    "6610b25967cdcca9d59875f5cb50b0ea75433311869e930b",
+    "68a79eaca2324873eacc50cb9c6eca8cc68ea5d936f98787c60c7ebc74e6ce7c",
+    "c0ba5a8e914111210f2bd131f3d5e08d",
+    "6d9be1ee6ebd27a258115aad99b7317b9c8d28b6d76431c3",
+    "9f6a2878b2520799a44ef18bc7df394e7061a224d2c33cd015b157d746869863",
+    "23db8160a31d3e0dca3688ed941adbf3",
+    "8197a4a47f0425faeaa69deebc05ca29c0a5b5cc76ceacc0",
+    "066dca1a2bb7e8a1db2832148ce9933eea0f3ac9548d793112d9a95c9407efad",
+    "f30f8c1da665478f49b001d94c5fc452",
+    "c10ec20dc3cd9f652c7fac2f1230f7a3c828389a14392f05",
+    "f585c11aec520db57dd353c69554b21a89b20fb0650966fa0a9d6f74fd989d8f",
+    // "park remain person kitchen mule spell knee armed position rail grid ankle"
+    "a056b28d3d8915a25ee05ea8761d9984",
+    // "crop cash unable insane eight faith inflict route frame loud box vibrant"
+    "33e46bb13a746ea41cdde45c90846a79",
+    // "silly laptop awake length nature thunder category claim reveal supply attitude drip"
+    "c8afa040c00937c309094db87b383aa1",
+    // "forum undo fragile fade shy sign arrest garment culture tube off merit", SeedQR
+    "5bbd9d71a8ec7990831aff359d426545",
+    // "attack pizza motion avocado network gather crop fresh patrol unusual wild holiday candy
+    // pony ranch winter theme error hybrid van cereal salon goddess expire", SeedQR
+    "0e74b64107f94cc0ccfae6a13dcbec3662154fec67e0e00999c07892597d190a",
+    // "shield group erode awake lock sausage cash glare wave crew flame glove", UR
+    "c5acdd320818377f48d316f7e6696131",
+    // "any paddle cabbage armor atom satoshi fiction night wisdom nasty they midnight chicken
+    // play phone"
+    "0a33d87e85f0e57ed574acfc52678246327b4ca8",
+    // "soda oak spy claim best oppose gun ghost school use sign shock sign pipe vote follow
+    // category filter"
+    "ce32fb4d14d1553719fb0cc0ddf721632c854abd7ad5242a",
+    // "quality useless orient offer pole host amazing title only clog sight wild anxiety gloom
+    // market rescue fan language entry fan oyster"
+    "af3e02724cba78dc01f7179b057320fd80a0c6e20db952cf9d2ea969",
+    // "always guess retreat devote warm poem giraffe thought prize ready maple daughter girl
+    // feel clay silent lemon bracket abstract basket toe tiny sword world"
+    "078ceee01e6f734e188f07ab565a1e1be624a98a8e437fc35803899e39c4f717",
+    // "test test test test test test test test test test test junk", Hardhat and Foundry
+    "df9bf37e6fcdf9bf37e6fcdf9bf37e3c",
+    // "candy maple cake sugar pudding cream honey rich smooth crumble sweet treat", Ganache
+    "2150f0816c6ad265db4dcacce69b6ef3",
+    // "myth like bonus scare over problem client lizard pioneer submit female collect", Truffle
+    "92903465e029df56cab416a53b015396",
+];
+
+/// Times a word has to be used to be flagged. A word used twice turns up in about one in seven
+/// random 24 word phrases, three times in about one in two thousand.
+const REPEAT_LIMIT: usize = 3;
+
+/// Length of a run of consecutive word indices that gets flagged
+const SEQUENCE_LIMIT: usize = 3;
+
+/// A reason not to trust a [`Mnemonic`][Mnemonic], see
+/// [`Mnemonic::weakness_report()`][Mnemonic::weakness_report()]
+///
+/// Word positions count from `0`.
+///
+/// [Mnemonic]: ../mnemonic/struct.Mnemonic.html
+/// [Mnemonic::weakness_report()]: ../mnemonic/struct.Mnemonic.html#method.weakness_report
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Weakness {
+    /// The phrase is published, as a test vector, a documentation example or the default of a
+    /// development tool
+    KnownPhrase,
+    /// Every byte of the entropy is the same, like all zeroes or all ones
+    ConstantEntropy,
+    /// The entropy repeats a short run of bytes, or counts up or down
+    PatternedEntropy,
+    /// The word at `position` is used `count` times, three or more
+    RepeatedWord { position: usize, count: usize },
+    /// Only this many distinct words, at least three fewer than the length of the phrase
+    FewDistinctWords(usize),
+    /// Three or more words starting at this position are consecutive in the word list
+    SequentialWords(usize),
+}
+
+impl fmt::Display for Weakness {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Weakness::KnownPhrase => f.write_str("the phrase is published"),
+            Weakness::ConstantEntropy => f.write_str("every byte of the entropy is the same"),
+            Weakness::PatternedEntropy => f.write_str("the entropy follows a pattern"),
+            Weakness::RepeatedWord { position, count } => {
+                write!(f, "word at position {} is used {} times", position, count)
+            }
+            Weakness::FewDistinctWords(distinct) => {
+                write!(f, "only {} distinct words", distinct)
+            }
+            Weakness::SequentialWords(position) => write!(
+                f,
+                "words from position {} are consecutive in the word list",
+                position
+            ),
+        }
+    }
+}
+
+/// The result of [`Mnemonic::weakness_report()`][Mnemonic::weakness_report()]
+///
+/// [Mnemonic::weakness_report()]: ../mnemonic/struct.Mnemonic.html#method.weakness_report
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct WeaknessReport {
+    weaknesses: Vec<Weakness>,
+}
+
+impl WeaknessReport {
+    /// Whether anything was flagged
+    pub fn is_weak(&self) -> bool {
+        !self.weaknesses.is_empty()
+    }
+
+    /// Everything that was flagged, in a fixed order: known phrases, then entropy patterns, then
+    /// word patterns
+    pub fn weaknesses(&self) -> &[Weakness] {
+        &self.weaknesses
+    }
+}
+
+impl Mnemonic {
+    /// Check the mnemonic for signs that it is published, made up, or generated from broken
+    /// entropy
+    ///
+    /// Known phrases are matched by entropy, in constant time, so the same test vector in another
+    /// language is caught too. The other checks are set so that a randomly generated phrase is
+    /// flagged with a probability of well under one in a thousand.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bip39::{Mnemonic, Language, Weakness};
+    ///
+    /// let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+    /// let mnemonic = Mnemonic::from_phrase(phrase, Language::English).unwrap();
+    ///
+    /// let report = mnemonic.weakness_report();
+    ///
+    /// assert!(report.is_weak());
+    /// assert_eq!(report.weaknesses()[0], Weakness::KnownPhrase);
+    /// ```
+    pub fn weakness_report(&self) -> WeaknessReport {
+        let mut weaknesses = Vec::new();
+        let entropy = self.entropy();
+
+        let known = KNOWN_ENTROPY.iter().fold(Choice::from(0), |found, known| {
+            found | entropy.ct_eq(decode_hex(known).as_slice())
+        });
+
+        if known.into() {
+            weaknesses.push(Weakness::KnownPhrase);
+        }
+
+        if entropy.iter().all(|&byte| byte == entropy[0]) {
+            weaknesses.push(Weakness::ConstantEntropy);
+        } else if is_patterned(entropy) {
+            weaknesses.push(Weakness::PatternedEntropy);
+        }
+
+        let indices = self.word_indices();
+        let mut distinct = 0;
+
+        for (position, index) in indices.iter().enumerate() {
+            if indices[..position].contains(index) {
+                continue;
+            }
+
+            let count = indices.iter().filter(|&other| other == index).count();
+
+            if count >= REPEAT_LIMIT {
+                weaknesses.push(Weakness::RepeatedWord { position, count });
+            }
+
+            distinct += 1;
+        }
+
+        if distinct + REPEAT_LIMIT <= indices.len() {
+            weaknesses.push(Weakness::FewDistinctWords(distinct));
+        }
+
+        let mut run = 1;
+
+        for (position, pair) in indices.windows(2).enumerate() {
+            let step = pair[1].wrapping_sub(pair[0]);
+
+            if step != 1 && step != u16::MAX {
+                run = 1;
+                continue;
+            }
+
+            run += 1;
+
+            if run == SEQUENCE_LIMIT {
+                weaknesses.push(Weakness::SequentialWords(position + 2 - SEQUENCE_LIMIT));
+            }
+        }
+
+        WeaknessReport { weaknesses }
+    }
+}
+
+/// Whether the entropy repeats with a period of at most half its length, or steps by a constant
+fn is_patterned(entropy: &[u8]) -> bool {
+    let repeats = (1..=entropy.len() / 2)
+        .any(|period| entropy.iter().zip(&entropy[period..]).all(|(a, b)| a == b));
+
+    let step = entropy[1].wrapping_sub(entropy[0]);
+    let counts = entropy
+        .windows(2)
+        .all(|pair| pair[1].wrapping_sub(pair[0]) == step);
+
+    repeats || counts
+}
+
+fn decode_hex(hex: &str) -> Vec<u8> {
+    hex.as_bytes()
+        .chunks(2)
+        .map(|pair| {
+            let digits = std::str::from_utf8(pair).unwrap();
+
+            u8::from_str_radix(digits, 16).unwrap()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::language::Language;
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    fn report(phrase: &str) -> Vec<Weakness> {
+        let mnemonic = Mnemonic::from_phrase(phrase, Language::English).unwrap();
+
+        mnemonic.weakness_report().weaknesses().to_vec()
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn known_phrases() {
+        assert_eq!(
+            report("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about"),
+            [
+                Weakness::KnownPhrase,
+                Weakness::ConstantEntropy,
+                Weakness::RepeatedWord {
+                    position: 0,
+                    count: 11
+                },
+                Weakness::FewDistinctWords(2),
+            ]
+        );
+        assert_eq!(
+            report("park remain person kitchen mule spell knee armed position rail grid ankle"),
+            [Weakness::KnownPhrase]
+        );
+        assert_eq!(
+            report("forum undo fragile fade shy sign arrest garment culture tube off merit"),
+            [Weakness::KnownPhrase]
+        );
+        assert_eq!(
+            report("shield group erode awake lock sausage cash glare wave crew flame glove"),
+            [Weakness::KnownPhrase]
+        );
+        assert_eq!(
+            report("test test test test test test test test test test test junk"),
+            [
+                Weakness::KnownPhrase,
+                Weakness::RepeatedWord {
+                    position: 0,
+                    count: 11
+                },
+                Weakness::FewDistinctWords(2),
+            ]
+        );
+
+        for known in KNOWN_ENTROPY {
+            let mnemonic = Mnemonic::from_entropy(&decode_hex(known), Language::English).unwrap();
+
+            assert_eq!(
+                mnemonic.weakness_report().weaknesses()[0],
+                Weakness::KnownPhrase
+            );
+        }
+    }
+
+    #[cfg(feature = "default-langs")]
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn known_phrases_in_other_languages() {
+        let entropy = decode_hex("9e885d952ad362caeb4efe34a8e91bd2");
+        let mnemonic = Mnemonic::from_entropy(&entropy, Language::Japanese).unwrap();
+
+        assert_eq!(
+            mnemonic.weakness_report().weaknesses(),
+            [Weakness::KnownPhrase]
+        );
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn patterned_entropy() {
+        let counting: Vec<u8> = (0..16).collect();
+        let mnemonic = Mnemonic::from_entropy(&counting, Language::English).unwrap();
+
+        assert_eq!(
+            mnemonic.weakness_report().weaknesses(),
+            [Weakness::PatternedEntropy]
+        );
+
+        let repeating = decode_hex("0123456789abcdef0123456789abcdef");
+        let mnemonic = Mnemonic::from_entropy(&repeating, Language::English).unwrap();
+
+        assert_eq!(
+            mnemonic.weakness_report().weaknesses(),
+            [Weakness::PatternedEntropy]
+        );
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn sequential_words() {
+        let mut indices = [
+            736, 1703, 1046, 1015, 1685, 402, 56, 1257, 1921, 1759, 692, 0,
+        ];
+
+        indices[4..7].copy_from_slice(&[1001, 1000, 999]);
+
+        // Try every last word until the checksum matches
+        let mnemonic = (0..2048)
+            .find_map(|last| {
+                indices[11] = last;
+                Mnemonic::from_word_indices(&indices, Language::English).ok()
+            })
+            .unwrap();
+
+        assert_eq!(
+            mnemonic.weakness_report().weaknesses(),
+            [Weakness::SequentialWords(4)]
+        );
+    }
+
+    #[cfg_attr(all(target_arch = "wasm32"), wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn random_phrase_is_not_weak() {
+        let phrase = "foster stay lizard leave sport crane alpha outdoor usual swim final evil";
+        let mnemonic = Mnemonic::from_phrase(phrase, Language::English).unwrap();
+
+        assert!(!mnemonic.weakness_report().is_weak());
+        assert_eq!(
+            Weakness::RepeatedWord {
+                position: 2,
+                count: 3
+            }
+            .to_string(),
+            "word at position 2 is used 3 times"
+        );
+        assert_eq!(
+            Weakness::SequentialWords(4).to_string(),
+            "words from position 4 are consecutive in the word list"
+        );
+    }
+}
@@ -0,0 +1,46 @@
+//! Word lists and lookups for every compiled in [`Language`][Language].
+//!
+//! Get the list for a language with [`Language::wordlist()`][wordlist] and the word -> index map
+//! with [`Language::wordmap()`][wordmap]. Every list has 2048 words in NFKD form, and the
+//! position of a word is its 11-bit value in a phrase.
+//!
+//! | Language              | Code      | Feature               | Unique prefix length |
+//! |-----------------------|-----------|-----------------------|----------------------|
+//! | `English`             | `en`      | always included       | 4                    |
+//! | `ChineseSimplified`   | `zh-hans` | `chinese-simplified`  | 1                    |
+//! | `ChineseTraditional`  | `zh-hant` | `chinese-traditional` | 1                    |
+//! | `Czech`               | `cs`      | `czech`               | 4                    |
+//! | `French`              | `fr`      | `french`              | 6                    |
+//! | `Italian`             | `it`      | `italian`             | 4                    |
+//! | `Japanese`            | `ja`      | `japanese`            | 5                    |
+//! | `Korean`              | `ko`      | `korean`              | 5                    |
+//! | `Portuguese`          | `pt`      | `portuguese`          | 4                    |
+//! | `Spanish`             | `es`      | `spanish`             | 5                    |
+//!
+//! The unique prefix length is counted in NFKD characters, see
+//! [`WordList::unique_prefix_len()`][unique_prefix_len].
+//!
+//! Other lists can be loaded at runtime with [`CustomWordList`][CustomWordList] and used through
+//! [`Language::custom()`][custom].
+//!
+//! ```
+//! use bip39::Language;
+//!
+//! let wordlist = Language::English.wordlist();
+//!
+//! assert_eq!(wordlist.len(), 2048);
+//! assert_eq!(wordlist.get(2047), Some("zoo"));
+//! assert_eq!(wordlist.index_of("zoo"), Some(2047));
+//! assert_eq!(wordlist.get_words_by_prefix("woo"), ["wood", "wool"]);
+//! assert_eq!(wordlist.unique_prefix_len(), 4);
+//! ```
+//!
+//! [Language]: ../enum.Language.html
+//! [wordlist]: ../enum.Language.html#method.wordlist
+//! [wordmap]: ../enum.Language.html#method.wordmap
+//! [unique_prefix_len]: ./struct.WordList.html#method.unique_prefix_len
+//! [CustomWordList]: ./struct.CustomWordList.html
+//! [custom]: ../enum.Language.html#method.custom
+
+pub use crate::language::{CustomLanguage, CustomWordList, WordList, WordMap};
+pub use crate::util::Bits11;
@@ -0,0 +1,102 @@
+#!/usr/bin/env python3
+"""Generate the keystore test vectors in src/keystore.rs without using the crate.
+
+The phrase is decoded with the official English word list, the key is derived with
+Python's hashlib.scrypt or the Argon2id of the `cryptography` package, and the entropy is
+encrypted with XChaCha20-Poly1305, built from an HChaCha20 written out here and the
+ChaCha20-Poly1305 of `cryptography`.
+
+    pip install 'cryptography>=44'
+    python3 tests/keystore_vectors.py
+"""
+
+import hashlib
+import struct
+import unicodedata
+from pathlib import Path
+
+from cryptography.hazmat.primitives.ciphers.aead import ChaCha20Poly1305
+from cryptography.hazmat.primitives.kdf.argon2 import Argon2id
+
+PHRASE = "crop cash unable insane eight faith inflict route frame loud box vibrant"
+PASSWORD = "hunter2"
+METADATA = b"test"
+SALT = bytes([0x11] * 16)
+NONCE = bytes([0x22] * 24)
+
+WORDLIST = Path(__file__).resolve().parent.parent / "src" / "langs" / "english.txt"
+
+
+def phrase_to_entropy(phrase):
+    words = WORDLIST.read_text().split()
+    bits = "".join(format(words.index(word), "011b") for word in phrase.split())
+    checksum_len = len(bits) // 33
+    entropy = int(bits[:-checksum_len], 2).to_bytes(len(bits) // 33 * 4, "big")
+    checksum = format(hashlib.sha256(entropy).digest()[0], "08b")[:checksum_len]
+
+    assert bits[-checksum_len:] == checksum
+    return entropy
+
+
+def hchacha20(key, nonce):
+    """HChaCha20 as specified in draft-irtf-cfrg-xchacha, section 2.2"""
+
+    def rotl(value, shift):
+        return ((value << shift) & 0xFFFFFFFF) | (value >> (32 - shift))
+
+    def quarter_round(state, a, b, c, d):
+        state[a] = (state[a] + state[b]) & 0xFFFFFFFF
+        state[d] = rotl(state[d] ^ state[a], 16)
+        state[c] = (state[c] + state[d]) & 0xFFFFFFFF
+        state[b] = rotl(state[b] ^ state[c], 12)
+        state[a] = (state[a] + state[b]) & 0xFFFFFFFF
+        state[d] = rotl(state[d] ^ state[a], 8)
+        state[c] = (state[c] + state[d]) & 0xFFFFFFFF
+        state[b] = rotl(state[b] ^ state[c], 7)
+
+    state = list(struct.unpack("<4I", b"expand 32-byte k"))
+    state += struct.unpack("<8I", key)
+    state += struct.unpack("<4I", nonce)
+
+    for _ in range(10):
+        quarter_round(state, 0, 4, 8, 12)
+        quarter_round(state, 1, 5, 9, 13)
+        quarter_round(state, 2, 6, 10, 14)
+        quarter_round(state, 3, 7, 11, 15)
+        quarter_round(state, 0, 5, 10, 15)
+        quarter_round(state, 1, 6, 11, 12)
+        quarter_round(state, 2, 7, 8, 13)
+        quarter_round(state, 3, 4, 9, 14)
+
+    return struct.pack("<8I", *(state[0:4] + state[12:16]))
+
+
+def xchacha20poly1305_encrypt(key, nonce, message, aad):
+    subkey = hchacha20(key, nonce[:16])
+
+    return ChaCha20Poly1305(subkey).encrypt(b"\0" * 4 + nonce[16:], message, aad)
+
+
+def keystore(kdf, params):
+    password = unicodedata.normalize("NFKD", PASSWORD).encode()
+
+    if kdf == "scrypt":
+        log_n, r, p = params
+        kdf_header = bytes([1, log_n]) + struct.pack(">II", r, p)
+        key = hashlib.scrypt(password, salt=SALT, n=1 << log_n, r=r, p=p, dklen=32)
+    else:
+        m_cost, t_cost, p_cost = params
+        kdf_header = bytes([2]) + struct.pack(">III", m_cost, t_cost, p_cost)
+        key = Argon2id(
+            salt=SALT, length=32, iterations=t_cost, lanes=p_cost, memory_cost=m_cost
+        ).derive(password)
+
+    header = b"B39K" + bytes([1]) + kdf_header + SALT + NONCE
+    header += bytes([2]) + b"en" + struct.pack(">H", len(METADATA)) + METADATA
+
+    return header + xchacha20poly1305_encrypt(key, NONCE, phrase_to_entropy(PHRASE), header)
+
+
+if __name__ == "__main__":
+    print("SCRYPT_VECTOR:  ", keystore("scrypt", (10, 8, 1)).hex())
+    print("ARGON2ID_VECTOR:", keystore("argon2id", (64, 1, 1)).hex())
@@ -99,6 +99,10 @@ test_maybe_wasm!(validate_chinese_traditional, #[cfg(feature = "chinese-traditio
     validate_language(Language::ChineseTraditional);
 });
 
+test_maybe_wasm!(validate_czech, #[cfg(feature = "czech")], {
+    validate_language(Language::Czech);
+});
+
 test_maybe_wasm!(validate_french, #[cfg(feature = "french")], {
     validate_language(Language::French);
 });
@@ -115,6 +119,10 @@ test_maybe_wasm!(validate_korean, #[cfg(feature = "korean")], {
     validate_language(Language::Korean);
 });
 
+test_maybe_wasm!(validate_portuguese, #[cfg(feature = "portuguese")], {
+    validate_language(Language::Portuguese);
+});
+
 test_maybe_wasm!(validate_spanish, #[cfg(feature = "spanish")], {
     validate_language(Language::Spanish);
 });
@@ -0,0 +1,150 @@
+//! Checks that no secret material is left behind in freed heap memory.
+//!
+//! A global allocator inspects every block as it's freed and counts blocks that still contain
+//! the leading bytes of the entropy, the phrase, or the seed used below.
+#![cfg(not(target_arch = "wasm32"))]
+
+use bip39::{Language, Mnemonic, Seed};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::slice;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use zeroize::Zeroizing;
+
+const ENTROPY: [u8; 16] = [
+    0x33, 0xE4, 0x6B, 0xB1, 0x3A, 0x74, 0x6E, 0xA4, 0x1C, 0xDD, 0xE4, 0x5C, 0x90, 0x84, 0x6A, 0x79,
+];
+const PHRASE: &str = "crop cash unable insane eight faith inflict route frame loud box vibrant";
+const SEED: [u8; 8] = [0x0b, 0xde, 0x96, 0xf1, 0x4c, 0x35, 0xa6, 0x62];
+const PASSWORD: &str = "hunter2hunter2";
+
+/// Prefixes searched for in freed memory, long enough not to occur by accident
+const NEEDLES: [&[u8]; 4] = [
+    &[0x33, 0xE4, 0x6B, 0xB1, 0x3A, 0x74],
+    b"crop cash",
+    &SEED,
+    b"hunter2h",
+];
+
+static SCANNING: AtomicBool = AtomicBool::new(false);
+static LEAKS: AtomicUsize = AtomicUsize::new(0);
+static SERIAL: Mutex<()> = Mutex::new(());
+
+struct ScanningAllocator;
+
+unsafe impl GlobalAlloc for ScanningAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        if SCANNING.load(Ordering::SeqCst) {
+            let block = slice::from_raw_parts(ptr, layout.size());
+
+            if NEEDLES
+                .iter()
+                .any(|needle| block.windows(needle.len()).any(|w| w == *needle))
+            {
+                LEAKS.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: ScanningAllocator = ScanningAllocator;
+
+/// Run `f` with scanning enabled, return the number of leaked blocks
+fn leaks(f: impl FnOnce()) -> usize {
+    let _guard = SERIAL.lock().unwrap_or_else(|err| err.into_inner());
+
+    LEAKS.store(0, Ordering::SeqCst);
+    SCANNING.store(true, Ordering::SeqCst);
+    f();
+    SCANNING.store(false, Ordering::SeqCst);
+
+    LEAKS.load(Ordering::SeqCst)
+}
+
+#[test]
+fn from_entropy() {
+    assert_eq!(
+        leaks(|| {
+            let mnemonic = Mnemonic::from_entropy(&ENTROPY, Language::English).unwrap();
+            assert_eq!(mnemonic.phrase(), PHRASE);
+        }),
+        0
+    );
+}
+
+#[test]
+fn from_phrase() {
+    assert_eq!(
+        leaks(|| {
+            let mnemonic = Mnemonic::from_phrase(PHRASE, Language::English).unwrap();
+            assert_eq!(mnemonic.entropy(), ENTROPY);
+        }),
+        0
+    );
+}
+
+#[test]
+fn validate() {
+    assert_eq!(
+        leaks(|| Mnemonic::validate(PHRASE, Language::English).unwrap()),
+        0
+    );
+}
+
+#[test]
+fn validate_invalid_checksum() {
+    let phrase = PHRASE.replace("vibrant", "zoo");
+
+    assert_eq!(
+        leaks(|| assert!(Mnemonic::validate(&phrase, Language::English).is_err())),
+        0
+    );
+}
+
+#[test]
+fn into_phrase() {
+    assert_eq!(
+        leaks(|| {
+            let mnemonic = Mnemonic::from_entropy(&ENTROPY, Language::English).unwrap();
+            assert_eq!(*mnemonic.into_phrase(), PHRASE);
+        }),
+        0
+    );
+}
+
+#[test]
+fn seed() {
+    assert_eq!(
+        leaks(|| {
+            let mnemonic = Mnemonic::from_entropy(&ENTROPY, Language::English).unwrap();
+            let seed = Seed::new(&mnemonic, "password");
+            assert_eq!(&seed.as_bytes()[..8], SEED);
+        }),
+        0
+    );
+}
+
+#[test]
+fn seed_with_secret() {
+    assert_eq!(
+        leaks(|| {
+            let mnemonic = Mnemonic::from_entropy(&ENTROPY, Language::English).unwrap();
+            let password = Zeroizing::new(String::from(PASSWORD));
+            let seed = Seed::new_with_secret(&mnemonic, &password);
+            assert_eq!(seed.as_bytes().len(), 64);
+        }),
+        0
+    );
+}
+
+#[test]
+fn scanner_detects_leaks() {
+    assert_eq!(leaks(|| drop(PHRASE.to_string())), 1);
+}